@@ -0,0 +1,187 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::Error;
+
+/// The answer produced by running one part of a challenge.
+///
+/// Keeping the kind of answer around (rather than stringifying straight away) lets the runner
+/// compare numeric answers numerically and emit them as numbers in structured output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Str(String),
+    /// Answers spanning several lines, such as letters drawn in ASCII art
+    Multiline(Vec<String>),
+}
+
+impl Answer {
+    /// Get the answer as a number, if it is one.
+    pub fn as_number(&self) -> Option<i128> {
+        match self {
+            Self::Int(n) => Some(*n as i128),
+            Self::UInt(n) => Some(*n as i128),
+            _ => None,
+        }
+    }
+
+    /// Encode the answer on a single line, tagged with its kind, such that it can be written to
+    /// a file and read back with [`Answer::from_str`].
+    ///
+    /// Backslashes and line breaks in string answers are escaped, and the lines of a multiline
+    /// answer are separated by `\n` escapes.
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Int(n) => format!("int:{}", n),
+            Self::UInt(n) => format!("uint:{}", n),
+            Self::Str(s) => format!("str:{}", escape(s)),
+            Self::Multiline(lines) => format!(
+                "lines:{}",
+                lines
+                    .iter()
+                    .map(|line| escape(line))
+                    .collect::<Vec<_>>()
+                    .join("\\n")
+            ),
+        }
+    }
+
+    /// Whether two answers are the same, comparing numbers by value regardless of whether
+    /// they're signed.
+    pub fn same_as(&self, other: &Answer) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    /// Displays the answer as it would be submitted.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{}", n),
+            Self::UInt(n) => write!(f, "{}", n),
+            Self::Str(s) => write!(f, "{}", s),
+            Self::Multiline(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once(':').ok_or_else(|| Error::invalid_answer(s))?;
+
+        let answer = match kind {
+            "int" => Self::Int(value.parse()?),
+            "uint" => Self::UInt(value.parse()?),
+            "str" => Self::Str(unescape(value).ok_or_else(|| Error::invalid_answer(s))?),
+            "lines" => Self::Multiline(
+                unescape(value)
+                    .ok_or_else(|| Error::invalid_answer(s))?
+                    .split('\n')
+                    .map(String::from)
+                    .collect(),
+            ),
+            _ => return Err(Error::invalid_answer(s)),
+        };
+
+        Ok(answer)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Int(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::UInt(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::UInt(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Self::Multiline(lines)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => result.push('\n'),
+                '\\' => result.push('\\'),
+                _ => return None,
+            },
+            c => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let answers = [
+            Answer::Int(-24000),
+            Answer::UInt(19),
+            Answer::from("MCD"),
+            Answer::from("back\\slash\nand newline"),
+            Answer::Multiline(vec!["#..#".to_string(), "####".to_string()]),
+        ];
+
+        for answer in answers {
+            let encoded = answer.encode();
+            assert!(!encoded.contains('\n'));
+            assert_eq!(encoded.parse::<Answer>().unwrap(), answer);
+        }
+
+        assert_eq!(
+            Answer::Multiline(vec!["#.".to_string(), ".#".to_string()]).to_string(),
+            "#.\n.#"
+        );
+        assert!("float:1.5".parse::<Answer>().is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Answer::Int(7).as_number(), Answer::UInt(7).as_number());
+        assert_eq!(Answer::from("7").as_number(), None);
+        assert!(Answer::Int(7).same_as(&Answer::UInt(7)));
+        assert!(!Answer::from("7").same_as(&Answer::UInt(7)));
+    }
+}
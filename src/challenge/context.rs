@@ -0,0 +1,188 @@
+use std::fmt::{self, Write as _};
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use super::ChallengePart;
+
+/// How much a challenge reports about itself while it runs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    /// Shown with `-v`
+    Debug = 1,
+    /// Shown with `-vv`
+    Trace = 2,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Debug => write!(f, "debug"),
+            Self::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// Where the frames of a visualization are shown.
+#[derive(Debug, Clone)]
+pub enum Frames {
+    /// Redraw each frame in place on stderr, pausing for this long after each one
+    Animate(Duration),
+    /// Write every frame to a file, one after another
+    File(Arc<Mutex<File>>),
+}
+
+/// Passed to a challenge's part while it runs, so it can log what it's doing and report its
+/// progress through long computations.
+///
+/// Everything is written to stderr, and only when the verbosity asks for it, so that the
+/// answers on stdout stay machine readable.
+#[derive(Debug, Clone)]
+pub struct Context {
+    day: usize,
+    part: ChallengePart,
+    verbosity: u8,
+    frames: Option<Frames>,
+}
+
+impl Context {
+    /// A context for a part, showing messages up to `verbosity` (the number of `-v`s given).
+    pub fn new(day: usize, part: ChallengePart, verbosity: u8) -> Self {
+        Context {
+            day,
+            part,
+            verbosity,
+            frames: None,
+        }
+    }
+
+    /// Show the frames of the part's visualization, if it has one.
+    pub fn with_frames(mut self, frames: Option<Frames>) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    fn enabled(&self, level: Level) -> bool {
+        self.verbosity >= level as u8
+    }
+
+    pub fn debug(&self, message: impl fmt::Display) {
+        self.log(Level::Debug, message);
+    }
+
+    pub fn trace(&self, message: impl fmt::Display) {
+        self.log(Level::Trace, message);
+    }
+
+    /// Report that `done` out of `total` steps are complete, e.g. `round 4,000/10,000`.
+    ///
+    /// This can be called on every step; only every tenth of the way and the final step are
+    /// shown, at the debug level.
+    pub fn progress(&self, label: &str, done: usize, total: usize) {
+        if self.enabled(Level::Debug) {
+            if let Some(message) = progress_message(label, done, total) {
+                self.log(Level::Debug, message);
+            }
+        }
+    }
+
+    /// Whether frames are being shown, so that parts only render them when they're wanted.
+    pub fn visualizing(&self) -> bool {
+        self.frames.is_some()
+    }
+
+    /// Show the next frame of the part's visualization.
+    pub fn frame(&self, frame: impl fmt::Display) {
+        let mut text = String::new();
+        // nothing sensible can be done if the frame can't be written, as with log messages
+        match &self.frames {
+            None => {}
+            Some(Frames::Animate(delay)) => {
+                // clear the screen and return to its top left corner before drawing
+                let _ = writeln!(text, "\x1b[2J\x1b[H{}", frame);
+                let _ = io::stderr().lock().write_all(text.as_bytes());
+                thread::sleep(*delay);
+            }
+            Some(Frames::File(file)) => {
+                let _ = writeln!(text, "{}\n", frame);
+                if let Ok(mut file) = file.lock() {
+                    let _ = file.write_all(text.as_bytes());
+                }
+            }
+        }
+    }
+
+    fn log(&self, level: Level, message: impl fmt::Display) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        // nothing sensible can be done if stderr can't be written to
+        let _ = writeln!(
+            io::stderr().lock(),
+            "[day {} {}] {}: {}",
+            self.day,
+            self.part,
+            level,
+            message
+        );
+    }
+}
+
+fn progress_message(label: &str, done: usize, total: usize) -> Option<String> {
+    let tenth = |n: usize| n * 10 / total.max(1);
+    if done != total && (done == 0 || tenth(done) == tenth(done - 1)) {
+        return None;
+    }
+
+    Some(format!(
+        "{} {}/{}",
+        label,
+        thousands(done),
+        thousands(total)
+    ))
+}
+
+/// Format a number with commas between groups of thousands.
+fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn progress() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(4000), "4,000");
+        assert_eq!(thousands(1234567), "1,234,567");
+
+        let shown = (1..=10_000)
+            .filter_map(|done| progress_message("round", done, 10_000))
+            .collect::<Vec<_>>();
+        assert_eq!(shown.len(), 10);
+        assert_eq!(shown[3], "round 4,000/10,000");
+        assert_eq!(shown[9], "round 10,000/10,000");
+
+        assert_eq!(progress_message("move", 3, 3), Some("move 3/3".to_string()));
+        assert_eq!(progress_message("move", 0, 0), Some("move 0/0".to_string()));
+
+        let ctx = Context::new(5, ChallengePart::First, 1);
+        assert!(ctx.enabled(Level::Debug));
+        assert!(!ctx.enabled(Level::Trace));
+    }
+}
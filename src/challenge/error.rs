@@ -1,85 +1,270 @@
+use std::fmt::{self, Write};
+use std::ops::Range;
+use std::time::Duration;
+
 use thiserror::Error;
 
+use super::ChallengePart;
 use crate::input;
 
+/// An error produced while setting up or running a challenge.
+///
+/// The day and part are attached by the runner, while the challenge itself attaches the
+/// input line that couldn't be parsed, if there is one.
+#[derive(Debug, Default)]
+pub struct Error {
+    pub(crate) day: Option<usize>,
+    pub(crate) part: Option<ChallengePart>,
+    pub(crate) line: Option<LineContext>,
+    pub(crate) kind: ErrorKind,
+}
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug, Default)]
-pub enum Error {
+pub enum ErrorKind {
     #[error("invalid day `{0}`")]
     InvalidDay(usize),
     #[error("day `{0}` not implemented")]
     DayNotImplemented(usize),
-    #[error("input error: {0}")]
+    #[error("year `{0}` not implemented")]
+    YearNotImplemented(usize),
+    #[error("error loading input")]
     InputError(#[from] input::error::Error),
-    #[error("error parsing int: {0}")]
+    #[error("error parsing int")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("missing data in challenge: {0}")]
     MissingDataError(String),
     #[error("invalid command in challenge: {0}")]
     InvalidCommandError(String),
+    #[error("unsupported character `{0}` in input")]
+    InvalidCharacterError(char),
+    #[error("invalid item `{0}` in rucksack")]
+    InvalidItemError(char),
+    #[error("invalid section assignment `{0}`")]
+    InvalidAssignmentError(String),
+    #[error("invalid encoded answer `{0}`")]
+    InvalidAnswerError(String),
     #[error("too many lines of input")]
     TooManyLinesError(),
     #[error("no solution found")]
     NoSolutionError(),
+    #[error("challenge panicked")]
+    PanicError(),
+    #[error("timed out after {0:.2?}")]
+    TimeoutError(Duration),
     #[error("unknown error")]
     #[default]
     UnknownError,
 }
 
-impl Error {
-    pub fn missing_data(name: &str) -> Self {
-        Error::MissingDataError(name.to_string())
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
     }
 }
 
-/*#[derive(Error, Debug, Default)]
-#[error("{source}")]
-pub struct Error {
-    pub(crate) day: usize,
-    #[source]
-    pub(crate) source: ErrorKind,
+// the kind is displayed as the error itself, so its source is passed through rather than the
+// kind being the source, which would repeat its message when the chain is printed
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.kind)
+    }
+}
+
+/// The line of puzzle input an error refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineContext {
+    /// 1-based line number within the input
+    pub number: usize,
+    pub text: String,
+    /// The characters within the line to point at
+    pub span: Range<usize>,
 }
 
 impl Error {
     pub(crate) fn invalid_day(day: usize) -> Self {
-        Error {
-            day,
-            source: ErrorKind::InvalidDay(day),
-        }
+        ErrorKind::InvalidDay(day).into()
     }
 
     pub(crate) fn not_implemented(day: usize) -> Self {
-        Error {
-            day,
-            source: ErrorKind::DayNotImplemented(day),
-        }
+        ErrorKind::DayNotImplemented(day).into()
     }
 
-    pub(crate) fn input_error(day: usize, err: input::error::Error) -> Self {
-        Error {
-            day,
-            source: ErrorKind::InputError(err),
-        }
+    pub(crate) fn year_not_implemented(year: usize) -> Self {
+        ErrorKind::YearNotImplemented(year).into()
     }
 
-    pub(crate) fn parse_int_error(day: usize, err: std::num::ParseIntError, num: String) -> Self {
-        Error {
-            day,
-            source: ErrorKind::ParseIntError(err, num),
-        }
+    pub(crate) fn missing_data(name: &str) -> Self {
+        ErrorKind::MissingDataError(name.to_string()).into()
     }
 
-    pub(crate) fn missing_data_error(day: usize, data: &str) -> Self {
-        Error {
-            day,
-            source: ErrorKind::MissingDataError(data.to_owned()),
+    pub(crate) fn invalid_command(command: &str) -> Self {
+        ErrorKind::InvalidCommandError(command.to_string()).into()
+    }
+
+    pub(crate) fn invalid_character(c: char) -> Self {
+        ErrorKind::InvalidCharacterError(c).into()
+    }
+
+    pub(crate) fn invalid_item(item: char) -> Self {
+        ErrorKind::InvalidItemError(item).into()
+    }
+
+    pub(crate) fn invalid_assignment(assignment: &str) -> Self {
+        ErrorKind::InvalidAssignmentError(assignment.to_string()).into()
+    }
+
+    pub(crate) fn invalid_answer(answer: &str) -> Self {
+        ErrorKind::InvalidAnswerError(answer.to_string()).into()
+    }
+
+    pub(crate) fn too_many_lines() -> Self {
+        ErrorKind::TooManyLinesError().into()
+    }
+
+    pub(crate) fn no_solution() -> Self {
+        ErrorKind::NoSolutionError().into()
+    }
+
+    pub(crate) fn panicked() -> Self {
+        ErrorKind::PanicError().into()
+    }
+
+    pub(crate) fn timed_out(elapsed: Duration) -> Self {
+        ErrorKind::TimeoutError(elapsed).into()
+    }
+
+    pub fn day(&self) -> Option<usize> {
+        self.day
+    }
+
+    pub fn part(&self) -> Option<ChallengePart> {
+        self.part
+    }
+
+    pub fn line(&self) -> Option<&LineContext> {
+        self.line.as_ref()
+    }
+
+    /// Attach the day that produced the error, unless one is already known.
+    pub(crate) fn in_day(mut self, day: usize) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    /// Attach the part that produced the error, unless one is already known.
+    pub(crate) fn in_part(mut self, part: ChallengePart) -> Self {
+        self.part.get_or_insert(part);
+        self
+    }
+
+    /// Attach the 1-based input line that caused the error, pointing at all of its
+    /// non-whitespace content.
+    pub(crate) fn at_line(self, number: usize, text: &str) -> Self {
+        let start = text.chars().take_while(|c| c.is_whitespace()).count();
+        let end = text.trim_end().chars().count().max(start);
+
+        self.at_span(number, text, start..end)
+    }
+
+    /// Attach the 1-based input line that caused the error, pointing at a range of characters
+    /// within it.
+    pub(crate) fn at_span(mut self, number: usize, text: &str, span: Range<usize>) -> Self {
+        if self.line.is_none() {
+            self.line = Some(LineContext {
+                number,
+                text: text.to_string(),
+                span,
+            });
         }
+
+        self
     }
+}
+
+impl LineContext {
+    /// Render the line with carets underneath the span, e.g.
+    ///
+    /// ```text
+    ///  --> input line 7
+    ///   |
+    /// 7 | move x from 1 to 2
+    ///   |      ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let number = self.number.to_string();
+        let gutter = " ".repeat(number.len());
+        let carets = "^".repeat(self.span.len().max(1));
+
+        let mut snippet = String::new();
+        // writing into a String can't fail
+        let _ = writeln!(snippet, "{}--> input line {}", gutter, self.number);
+        let _ = writeln!(snippet, "{} |", gutter);
+        let _ = writeln!(snippet, "{} | {}", number, self.text);
+        let _ = write!(
+            snippet,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.span.start),
+            carets
+        );
 
-    pub(crate) fn invalid_command_error(day: usize, command: &str) -> Self {
+        snippet
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
         Error {
-            day,
-            source: ErrorKind::InvalidCommandError(command.to_owned()),
+            kind,
+            ..Default::default()
         }
     }
 }
-*/
+
+impl From<input::error::Error> for Error {
+    fn from(err: input::error::Error) -> Self {
+        ErrorKind::from(err).into()
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ErrorKind::from(err).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn context() {
+        let err = Error::invalid_command("move x from 1 to 2")
+            .at_span(7, "move x from 1 to 2", 5..6)
+            .in_part(ChallengePart::First)
+            .in_day(5)
+            .in_day(6);
+
+        assert_eq!(err.day(), Some(5));
+        assert_eq!(err.part(), Some(ChallengePart::First));
+        assert_eq!(
+            err.line().unwrap().snippet(),
+            concat!(
+                " --> input line 7\n",
+                "  |\n",
+                "7 | move x from 1 to 2\n",
+                "  |      ^",
+            )
+        );
+    }
+
+    #[test]
+    fn whole_line() {
+        let err = Error::missing_data("number").at_line(12, "  abc ");
+        let line = err.line().unwrap();
+
+        assert_eq!(line.span, 2..5);
+        assert!(line.snippet().ends_with("   |   ^^^"));
+    }
+}
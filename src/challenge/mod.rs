@@ -1,29 +1,49 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
+macro_rules! challenge {
+    ($day:tt, $svc:expr => $mod:ident :: $struct:ident) => {{
+        let r#in = $svc.get_input($day).await?;
+        Ok(Box::new($mod::$struct::new(r#in)) as DynChallenge)
+    }};
+}
+
+mod answer;
+mod context;
 pub mod error;
+pub mod year2022;
 
 use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use clap::ValueEnum;
+use futures::future;
 use futures::stream::{self, FuturesOrdered, StreamExt};
+use tokio::sync::Semaphore;
+use tokio::{task, time};
 
+pub use answer::Answer;
+pub use context::{Context, Frames};
 use error::*;
 
-use crate::input::Input;
+use crate::input::InputSource;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 #[repr(i32)]
 pub enum ChallengePart {
+    #[value(alias = "1")]
     First = 1,
+    #[value(alias = "2")]
     Second = 2,
 }
 
+impl ChallengePart {
+    pub fn all() -> Vec<ChallengePart> {
+        vec![Self::First, Self::Second]
+    }
+}
+
 impl fmt::Display for ChallengePart {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -33,67 +53,433 @@ impl fmt::Display for ChallengePart {
     }
 }
 
-pub trait Challenge<T: AsRef<str>> {
-    fn run(&self, part: ChallengePart) -> Result<String>;
+pub trait Challenge {
+    fn run(&self, part: ChallengePart, ctx: &Context) -> Result<Answer>;
 }
 
-pub async fn run_all_challenges(input_svc: &Input) -> Result<Vec<Vec<String>>> {
-    let results = stream::iter(1..=25)
-        .map(|day| {
-            run_challenge(
-                day,
-                vec![ChallengePart::First, ChallengePart::Second],
-                input_svc,
+/// A challenge set up with its input, which can be shared with the threads that run its parts.
+type DynChallenge = Box<dyn Challenge + Send + Sync>;
+
+/// How challenges are run.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// How many parts may run at once
+    pub jobs: usize,
+    /// How long a part may run before it's abandoned
+    pub timeout: Option<Duration>,
+    /// How much the parts report about themselves on stderr
+    pub verbosity: u8,
+    /// Where the parts that can visualize how they work show their frames
+    pub frames: Option<Frames>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            jobs: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            timeout: None,
+            verbosity: 0,
+            frames: None,
+        }
+    }
+}
+
+/// The outcome of running a single part of a challenge.
+///
+/// Errors produced while solving a part are kept here rather than propagated, so that one
+/// failing part doesn't discard the answers of the others.
+#[derive(Debug)]
+pub struct PartResult {
+    pub day: usize,
+    pub part: ChallengePart,
+    pub answer: Result<Answer>,
+    pub duration: Duration,
+}
+
+/// Run both parts of every implemented day.
+///
+/// A day that can't be set up at all (for example, because its input couldn't be downloaded)
+/// is reported in place of its part results so the remaining days can still run.
+pub async fn run_all_challenges<S: InputSource>(
+    year: usize,
+    options: &RunOptions,
+    input_svc: &S,
+) -> Vec<Result<Vec<PartResult>>> {
+    let days = (1..=25).collect::<Vec<_>>();
+
+    run_challenges(year, &days, &ChallengePart::all(), options, input_svc)
+        .await
+        .into_iter()
+        .filter(|result| {
+            !matches!(
+                result,
+                Err(Error {
+                    kind: ErrorKind::DayNotImplemented(_),
+                    ..
+                })
             )
         })
+        .collect()
+}
+
+/// Run the given parts of each of the given days, with the results in day order.
+///
+/// Unlike [`run_all_challenges`], days that haven't been implemented are reported as failures,
+/// since they were asked for explicitly.
+///
+/// Every day is set up at once, while the parts themselves are CPU bound, so they're run on
+/// blocking threads with no more than `options.jobs` running at a time.
+///
+/// A part that runs for longer than `options.timeout` is reported as having timed out and its
+/// slot is given to the next part. The thread it was running on can't be stopped, though, so it
+/// carries on in the background until the part finishes or the process exits.
+pub async fn run_challenges<S: InputSource>(
+    year: usize,
+    days: &[usize],
+    parts: &[ChallengePart],
+    options: &RunOptions,
+    input_svc: &S,
+) -> Vec<Result<Vec<PartResult>>> {
+    let jobs = Arc::new(Semaphore::new(options.jobs.max(1)));
+
+    stream::iter(days)
+        .map(|day| run_day(year, *day, parts, &jobs, options, input_svc))
         .collect::<FuturesOrdered<_>>()
         .await
-        .collect::<Vec<_>>()
+        .collect()
         .await
-        .into_iter()
-        .filter_map(|res| match res {
-            result @ Ok(_) => Some(result),
-            Err(Error::DayNotImplemented(_)) => None,
-            result @ Err(_) => Some(result),
+}
+
+pub async fn run_challenge<S: InputSource>(
+    year: usize,
+    day: usize,
+    parts: Vec<ChallengePart>,
+    options: &RunOptions,
+    input_svc: &S,
+) -> Result<Vec<PartResult>> {
+    let jobs = Arc::new(Semaphore::new(options.jobs.max(1)));
+    run_day(year, day, &parts, &jobs, options, input_svc).await
+}
+
+async fn run_day<S: InputSource>(
+    year: usize,
+    day: usize,
+    parts: &[ChallengePart],
+    jobs: &Arc<Semaphore>,
+    options: &RunOptions,
+    input_svc: &S,
+) -> Result<Vec<PartResult>> {
+    let challenge = get_challenge(year, day, input_svc)
+        .await
+        .map_err(|err| err.in_day(day))?;
+
+    Ok(run_parts(Arc::new(challenge), day, parts, jobs, options).await)
+}
+
+/// Run the given parts of a day that's been set up, each on a blocking thread once one of the
+/// `jobs` is free.
+async fn run_parts(
+    challenge: Arc<DynChallenge>,
+    day: usize,
+    parts: &[ChallengePart],
+    jobs: &Arc<Semaphore>,
+    options: &RunOptions,
+) -> Vec<PartResult> {
+    let results = parts.iter().map(|&part| {
+        let challenge = Arc::clone(&challenge);
+        let jobs = Arc::clone(jobs);
+        let ctx = Context::new(day, part, options.verbosity).with_frames(options.frames.clone());
+
+        async move {
+            // the semaphore is never closed, so a permit is always handed out eventually
+            let _permit = jobs.acquire_owned().await.ok();
+
+            let start = Instant::now();
+            let handle = task::spawn_blocking(move || run_part(&challenge, &ctx, day, part));
+            let joined = match options.timeout {
+                Some(limit) => time::timeout(limit, handle).await,
+                None => Ok(handle.await),
+            };
+
+            let err = match joined {
+                Ok(Ok(result)) => return result,
+                Ok(Err(_)) => Error::panicked(),
+                Err(_) => Error::timed_out(start.elapsed()),
+            };
+
+            PartResult {
+                day,
+                part,
+                answer: Err(err.in_day(day).in_part(part)),
+                duration: start.elapsed(),
+            }
+        }
+    });
+
+    future::join_all(results).await
+}
+
+/// The timings of a part run several times over, for benchmarking.
+#[derive(Debug)]
+pub struct BenchResult {
+    pub day: usize,
+    pub part: ChallengePart,
+    /// The answer from the last run, or the error that stopped the benchmark
+    pub answer: Result<Answer>,
+    pub durations: Vec<Duration>,
+}
+
+impl BenchResult {
+    pub fn min(&self) -> Duration {
+        self.durations.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.durations.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.durations.is_empty() {
+            return Duration::ZERO;
+        }
+
+        self.durations.iter().sum::<Duration>() / self.durations.len() as u32
+    }
+}
+
+/// Run each of the given parts of a day `iterations` times, setting the day up only once.
+///
+/// A part that fails stops being run, and keeps the timings it had up to the failure. Parts
+/// report nothing about themselves while being benchmarked, so that the timings aren't skewed.
+pub async fn bench_challenge<S: InputSource>(
+    year: usize,
+    day: usize,
+    parts: &[ChallengePart],
+    iterations: usize,
+    input_svc: &S,
+) -> Result<Vec<BenchResult>> {
+    let challenge = get_challenge(year, day, input_svc)
+        .await
+        .map_err(|err| err.in_day(day))?;
+
+    let results = parts
+        .iter()
+        .map(|&part| {
+            let ctx = Context::new(day, part, 0);
+            let mut bench = BenchResult {
+                day,
+                part,
+                answer: Err(Error::default()),
+                durations: Vec::with_capacity(iterations),
+            };
+
+            for _ in 0..iterations.max(1) {
+                let result = run_part(&challenge, &ctx, day, part);
+                bench.durations.push(result.duration);
+                bench.answer = result.answer;
+
+                if bench.answer.is_err() {
+                    break;
+                }
+            }
+
+            bench
         })
-        .collect::<Result<Vec<_>>>()?;
+        .collect();
 
     Ok(results)
 }
 
-pub async fn run_challenge(
+fn run_part(
+    challenge: &DynChallenge,
+    ctx: &Context,
     day: usize,
-    parts: Vec<ChallengePart>,
-    input_svc: &Input,
-) -> Result<Vec<String>> {
-    let challenge = get_challenge(day, input_svc).await?;
+    part: ChallengePart,
+) -> PartResult {
+    let start = Instant::now();
+    let answer = challenge
+        .run(part, ctx)
+        .map_err(|err| err.in_day(day).in_part(part));
 
-    let result = parts
-        .into_iter()
-        .map(|part| challenge.run(part))
-        .collect::<Result<Vec<_>>>()?;
+    PartResult {
+        day,
+        part,
+        answer,
+        duration: start.elapsed(),
+    }
+}
 
-    Ok(result)
+/// The days implemented for a year, with the titles of their puzzles.
+pub fn registered_days(year: usize) -> &'static [(usize, &'static str)] {
+    match year {
+        2022 => year2022::DAYS,
+        _ => &[],
+    }
 }
 
-macro_rules! challenge {
-    ($day:tt, $svc:expr => $mod:ident :: $struct:ident) => {{
-        let r#in = $svc.get_input($day).await?;
-        Ok(Box::new($mod::$struct::new(r#in)) as Box<dyn Challenge<String>>)
-    }};
+async fn get_challenge<S: InputSource>(
+    year: usize,
+    day: usize,
+    input_svc: &S,
+) -> Result<DynChallenge> {
+    if day == 0 || day > 25 {
+        return Err(Error::invalid_day(day));
+    }
+
+    match year {
+        2022 => year2022::get_challenge(day, input_svc).await,
+        year => Err(Error::year_not_implemented(year)),
+    }
 }
 
-async fn get_challenge(day: usize, input_svc: &Input) -> Result<Box<dyn Challenge<String>>> {
-    let challenge = match day {
-        day @ 1 => challenge!(day, input_svc => day1::Day1),
-        day @ 2 => challenge!(day, input_svc => day2::Day2),
-        day @ 3 => challenge!(day, input_svc => day3::Day3),
-        day @ 4 => challenge!(day, input_svc => day4::Day4),
-        day @ 5 => challenge!(day, input_svc => day5::Day5),
-        day @ 6 => challenge!(day, input_svc => day6::Day6),
-        day if day > 25 => Err(Error::InvalidDay(day)),
-        day => Err(Error::DayNotImplemented(day)),
-    }?;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::MemorySource;
+
+    #[tokio::test]
+    async fn run_all() {
+        let source = MemorySource::new()
+            .with_input(2, "A Y\nB X\nC Z\n")
+            .with_input(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\nextra line\n");
+
+        let results = run_all_challenges(2022, &RunOptions::default(), &source).await;
+
+        // every implemented day is run, whether or not it has an input
+        assert_eq!(results.len(), registered_days(2022).len());
 
-    Ok(challenge)
+        let day1 = results[0].as_ref().unwrap_err();
+        assert_eq!(day1.day(), Some(1));
+        assert!(matches!(day1.kind, ErrorKind::InputError(_)));
+
+        let day2 = results[1].as_ref().unwrap();
+        let answers = day2
+            .iter()
+            .map(|result| result.answer.as_ref().unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(answers, vec![Answer::Int(15), Answer::Int(12)]);
+
+        let day6 = results[5].as_ref().unwrap();
+        assert_eq!(day6.len(), 2);
+        assert!(day6.iter().all(|result| result.answer.is_err()));
+    }
+
+    #[tokio::test]
+    async fn selected_days() {
+        let source = MemorySource::new().with_input(2, "A Y\nB X\nC Z\n");
+
+        // the days that are yet to be implemented change as days are added, so use the first
+        let registered = registered_days(2022);
+        let missing = (1..=25)
+            .find(|day| registered.iter().all(|(other, _)| other != day))
+            .unwrap_or(26);
+
+        let options = RunOptions {
+            jobs: 1,
+            ..Default::default()
+        };
+        let results = run_challenges(
+            2022,
+            &[2, missing],
+            &[ChallengePart::Second],
+            &options,
+            &source,
+        )
+        .await;
+        assert_eq!(results.len(), 2);
+
+        let day2 = results[0].as_ref().unwrap();
+        assert_eq!(day2.len(), 1);
+        assert_eq!(day2[0].answer.as_ref().unwrap(), &Answer::Int(12));
+
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(err.day(), Some(missing));
+        assert!(matches!(
+            err.kind,
+            ErrorKind::DayNotImplemented(_) | ErrorKind::InvalidDay(_)
+        ));
+    }
+
+    /// Panics in the first part, but answers the second.
+    struct Panicking;
+
+    impl Challenge for Panicking {
+        fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+            match part {
+                ChallengePart::First => panic!("the first part panics"),
+                ChallengePart::Second => Ok(Answer::Int(0)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn panicking_part() {
+        let jobs = Arc::new(Semaphore::new(2));
+        let results = run_parts(
+            Arc::new(Box::new(Panicking)),
+            1,
+            &ChallengePart::all(),
+            &jobs,
+            &RunOptions::default(),
+        )
+        .await;
+
+        let err = results[0].answer.as_ref().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::PanicError()));
+        assert_eq!(err.part(), Some(ChallengePart::First));
+        assert_eq!(results[1].answer.as_ref().unwrap(), &Answer::Int(0));
+    }
+
+    /// Takes far longer than any test's timeout in the first part, but answers the second
+    /// straight away.
+    struct Sleeping;
+
+    impl Challenge for Sleeping {
+        fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+            if part == ChallengePart::First {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+
+            Ok(Answer::Int(0))
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let jobs = Arc::new(Semaphore::new(1));
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+
+        let results = run_parts(
+            Arc::new(Box::new(Sleeping)),
+            3,
+            &ChallengePart::all(),
+            &jobs,
+            &options,
+        )
+        .await;
+
+        let err = results[0].answer.as_ref().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::TimeoutError(_)));
+        assert_eq!(err.day(), Some(3));
+
+        // the timed out part gives up its only job, so the second part still gets to run
+        assert_eq!(results[1].answer.as_ref().unwrap(), &Answer::Int(0));
+    }
+
+    #[tokio::test]
+    async fn bench() {
+        let source = MemorySource::new().with_input(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n");
+
+        let results = bench_challenge(2022, 6, &ChallengePart::all(), 3, &source)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.durations.len() == 3));
+        assert!(results[0].min() <= results[0].mean() && results[0].mean() <= results[0].max());
+        assert_eq!(results[1].answer.as_ref().unwrap(), &Answer::UInt(19));
+    }
 }
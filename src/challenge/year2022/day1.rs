@@ -0,0 +1,110 @@
+use itertools::Itertools;
+
+use super::error::Error;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::PuzzleInput;
+
+pub struct Day1 {
+    r#in: PuzzleInput,
+}
+
+impl Day1 {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        Self { r#in }
+    }
+
+    fn run_first(&self) -> Result<i64> {
+        // there's always at least one total, so there's always a maximum
+        Ok(self.get_totals()?.into_iter().max().unwrap_or_default())
+    }
+
+    fn run_second(&self) -> Result<i64> {
+        Ok(self.get_totals()?.into_iter().sorted().rev().take(3).sum())
+    }
+
+    fn get_totals(&self) -> Result<Vec<i64>> {
+        let totals = self
+            .r#in
+            .lines()
+            .enumerate()
+            .batching(|it| {
+                let nums = it
+                    .take_while(|(_, line)| !line.is_empty())
+                    .map(|(idx, line)| {
+                        line.parse::<i64>()
+                            .map_err(|err| Error::from(err).at_line(idx + 1, line))
+                    })
+                    .collect::<Vec<_>>();
+
+                if !nums.is_empty() {
+                    Some(nums)
+                } else {
+                    None
+                }
+            })
+            .map(|batch| {
+                batch
+                    .into_iter()
+                    .try_fold(0i64, |acc, next| next.map(|num| acc + num))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if totals.is_empty() {
+            return Err(Error::missing_data("calories of any elf"));
+        }
+
+        Ok(totals)
+    }
+}
+
+impl Challenge for Day1 {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+        match part {
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::challenge::error::ErrorKind;
+
+    #[test]
+    fn example() {
+        let input = PuzzleInput::from(include_str!("examples/day1"));
+
+        let challenge = Day1::new(input);
+
+        let result = challenge.run_first();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 24000);
+
+        let result = challenge.run_second();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 45000);
+    }
+
+    #[test]
+    fn invalid_line() {
+        let challenge = Day1::new(["1000", "", "20x0"].into_iter().collect());
+
+        let err = challenge.run_first().unwrap_err();
+        let line = err.line().unwrap();
+        assert_eq!(line.number, 3);
+        assert_eq!(line.text, "20x0");
+    }
+
+    #[test]
+    fn empty_input() {
+        let challenge = Day1::new(PuzzleInput::default());
+
+        let err = challenge.run_first().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingDataError(_)));
+
+        let err = challenge.run_second().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingDataError(_)));
+    }
+}
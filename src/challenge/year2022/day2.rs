@@ -0,0 +1,185 @@
+use super::error::Error;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::PuzzleInput;
+
+#[derive(Copy, Clone, Debug)]
+#[repr(i64)]
+enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl Move {
+    /// Get the score resulting from a round of rock-paper-scissors.
+    ///
+    /// Since rock = 1, paper = 2, and scissors = 3, and they win in descending order
+    /// (scissors beats paper, paper beats rock, rock beats scissors), the result
+    /// can be determined by subtracting the opponent move from our move. If we win
+    /// with scissors or paper, the result is 3 - 2 = 1 or 2 - 1 = 1 respectively.
+    /// If we throw rock, it wraps around back to scissors, so 1 - 3 = -2.
+    ///
+    /// The result will be 0 if we throw the same shape since the values will always be
+    /// the same. Any other result is a loss for us (it's the inverse of the win condition,
+    /// losing with -1 or 2)
+    fn score(opponent: &Move, player: &Move) -> i64 {
+        let shape_score = *player as i64;
+        let result = Self::round(opponent, player);
+
+        shape_score + result as i64
+    }
+
+    fn round(opponent: &Move, player: &Move) -> RoundResult {
+        match *player as i64 - *opponent as i64 {
+            1 | -2 => RoundResult::Win,
+            0 => RoundResult::Draw,
+            -1 | 2 => RoundResult::Lose,
+            n => panic!("unexpected result {}", n),
+        }
+    }
+
+    fn sign_to(opponent: &Move, result: &RoundResult) -> Move {
+        match result {
+            RoundResult::Win => Move::from(*opponent as i64 + 1),
+            RoundResult::Draw => *opponent,
+            RoundResult::Lose => Move::from(*opponent as i64 - 1),
+        }
+    }
+}
+
+impl TryFrom<char> for Move {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'A' | 'X' => Ok(Self::Rock),
+            'B' | 'Y' => Ok(Self::Paper),
+            'C' | 'Z' => Ok(Self::Scissors),
+            c => Err(Error::invalid_character(c)),
+        }
+    }
+}
+
+impl From<i64> for Move {
+    /// Numbers wrap around, so that the move after scissors is rock and the move before rock
+    /// is scissors.
+    fn from(n: i64) -> Self {
+        match n.rem_euclid(3) {
+            0 => Move::Scissors,
+            1 => Move::Rock,
+            _ => Move::Paper,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(i64)]
+enum RoundResult {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl TryFrom<char> for RoundResult {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'X' => Ok(Self::Lose),
+            'Y' => Ok(Self::Draw),
+            'Z' => Ok(Self::Win),
+            c => Err(Error::invalid_character(c)),
+        }
+    }
+}
+
+pub struct Day2 {
+    r#in: PuzzleInput,
+}
+
+impl Day2 {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        Self { r#in }
+    }
+
+    fn run_first(&self) -> Result<i64> {
+        Ok(self
+            .get_rounds::<Move>()?
+            .iter()
+            .map(|(opponent, player)| Move::score(opponent, player))
+            .sum())
+    }
+
+    fn run_second(&self) -> Result<i64> {
+        Ok(self
+            .get_rounds::<RoundResult>()?
+            .iter()
+            .map(|(opponent, result)| Move::score(opponent, &Move::sign_to(opponent, result)))
+            .sum())
+    }
+
+    /// Read the opponent's move and the second column of each round, which is interpreted
+    /// differently in each part.
+    fn get_rounds<R: TryFrom<char, Error = Error>>(&self) -> Result<Vec<(Move, R)>> {
+        self.r#in
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| Self::parse_round(line).map_err(|err| err.at_line(idx + 1, line)))
+            .collect()
+    }
+
+    fn parse_round<R: TryFrom<char, Error = Error>>(line: &str) -> Result<(Move, R)> {
+        let mut round = line.chars().filter(|c| c.is_ascii_alphabetic());
+
+        let left = round.next();
+        let right = round.next();
+
+        match (left, right) {
+            (Some(l), Some(r)) => Ok((Move::try_from(l)?, R::try_from(r)?)),
+            _ => Err(Error::missing_data("move in round")),
+        }
+    }
+}
+
+impl Challenge for Day2 {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+        match part {
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::challenge::error::ErrorKind;
+
+    #[test]
+    fn example() {
+        let input = PuzzleInput::from(include_str!("examples/day2"));
+
+        let challenge = Day2::new(input);
+
+        let result = challenge.run_first();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 15);
+
+        let result = challenge.run_second();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 12);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let challenge = Day2::new(["A Y", "B W"].into_iter().collect());
+        let err = challenge.run_first().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidCharacterError('W')));
+        assert_eq!(err.line().unwrap().number, 2);
+
+        let challenge = Day2::new(["A Y", "B"].into_iter().collect());
+        assert!(challenge.run_second().is_err());
+    }
+}
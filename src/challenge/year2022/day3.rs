@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use itertools::Itertools;
+
+use super::error::Error;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::PuzzleInput;
+
+pub struct Day3 {
+    r#in: PuzzleInput,
+    item_values: Vec<char>,
+}
+
+impl Day3 {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        let item_values = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+            .chars()
+            .collect::<Vec<_>>();
+
+        Self { r#in, item_values }
+    }
+
+    fn run_first(&self) -> Result<i64> {
+        let result = self
+            .r#in
+            .lines()
+            .enumerate()
+            .map(|(idx, sack)| {
+                let half = sack.len() / 2;
+                let (left, right) = (
+                    HashSet::<char>::from_iter(sack.chars().take(half)),
+                    HashSet::<char>::from_iter(sack.chars().skip(half)),
+                );
+
+                left.intersection(&right)
+                    .map(|c| self.priority_of(*c))
+                    .sum::<Result<i64>>()
+                    .map_err(|err| err.at_line(idx + 1, sack))
+            })
+            .sum::<Result<i64>>()?;
+
+        Ok(result)
+    }
+
+    fn run_second(&self) -> Result<i64> {
+        let result = self
+            .r#in
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .collect_vec()
+            .chunks(3)
+            .map(|chunk| {
+                if chunk.len() < 3 {
+                    // the group is cut short, so point at the last rucksack there is of it
+                    let (idx, sack) = chunk[chunk.len() - 1];
+                    return Err(
+                        Error::missing_data("rucksack to complete the group of three")
+                            .at_line(idx + 1, sack),
+                    );
+                }
+
+                let (first, second, third) = (
+                    HashSet::<char>::from_iter(chunk[0].1.chars()),
+                    HashSet::<char>::from_iter(chunk[1].1.chars()),
+                    HashSet::<char>::from_iter(chunk[2].1.chars()),
+                );
+
+                first
+                    .intersection(&second)
+                    .copied()
+                    .collect::<HashSet<_, _>>()
+                    .intersection(&third)
+                    .map(|c| self.priority_of(*c))
+                    .sum::<Result<i64>>()
+            })
+            .sum::<Result<i64>>()?;
+
+        Ok(result)
+    }
+
+    fn priority_of(&self, item: char) -> Result<i64> {
+        let index = self
+            .item_values
+            .iter()
+            .find_position(|c| **c == item)
+            .ok_or_else(|| Error::invalid_item(item))?
+            .0;
+
+        // score is 1-based, index is 0-based
+        Ok(index as i64 + 1)
+    }
+}
+
+impl Challenge for Day3 {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+        match part {
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::challenge::error::ErrorKind;
+
+    #[test]
+    fn example() {
+        let input = PuzzleInput::from(include_str!("examples/day3"));
+
+        let challenge = Day3::new(input);
+
+        let result = challenge.run_first();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 157);
+
+        let result = challenge.run_second();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 70);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let challenge = Day3::new(PuzzleInput::from("ab1cb1"));
+        let err = challenge.run_first().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidItemError('1')));
+
+        let challenge = Day3::new(["abca", "dbed", "fbgf", "hihj"].into_iter().collect());
+        let err = challenge.run_second().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingDataError(_)));
+        assert_eq!(err.line().map(|line| line.number), Some(4));
+
+        // blank lines don't count towards a group
+        let challenge = Day3::new(["abca", "", "dbed", "fbgf", "", ""].into_iter().collect());
+        assert_eq!(challenge.run_second().unwrap(), 2);
+    }
+}
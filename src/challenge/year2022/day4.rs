@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use super::error::Error;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::PuzzleInput;
+
+#[derive(Debug)]
+pub struct Assignment(i64, i64);
+
+impl Assignment {
+    fn contains(&self, other: &Assignment) -> bool {
+        self.0 <= other.0 && self.0 <= other.1 && self.1 >= other.1 && self.1 >= other.0
+    }
+
+    fn overlaps(&self, other: &Assignment) -> bool {
+        self.0 <= other.1 && self.0 >= other.0 || self.1 >= other.0 && self.1 <= other.1
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = Error;
+
+    fn from_str(d: &str) -> Result<Self> {
+        let (start, end) = d
+            .split_once('-')
+            .ok_or_else(|| Error::invalid_assignment(d))?;
+
+        Ok(Assignment(start.trim().parse()?, end.trim().parse()?))
+    }
+}
+
+pub struct Day4 {
+    r#in: PuzzleInput,
+}
+
+impl Day4 {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        Self { r#in }
+    }
+
+    fn run_first(&self) -> Result<i64> {
+        Ok(self
+            .get_assignments()?
+            .into_iter()
+            .filter(|(left, right)| left.contains(right) || right.contains(left))
+            .count() as i64)
+    }
+
+    fn run_second(&self) -> Result<i64> {
+        Ok(self
+            .get_assignments()?
+            .into_iter()
+            .filter(|(left, right)| left.overlaps(right) || right.overlaps(left))
+            .count() as i64)
+    }
+
+    fn get_assignments(&self) -> Result<Vec<(Assignment, Assignment)>> {
+        self.r#in
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| Self::parse_pair(line).map_err(|err| err.at_line(idx + 1, line)))
+            .collect()
+    }
+
+    fn parse_pair(line: &str) -> Result<(Assignment, Assignment)> {
+        let (left, right) = line
+            .split_once(',')
+            .ok_or_else(|| Error::invalid_assignment(line))?;
+
+        Ok((left.parse()?, right.parse()?))
+    }
+}
+
+impl Challenge for Day4 {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+        match part {
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = PuzzleInput::from(include_str!("examples/day4"));
+
+        let challenge = Day4::new(input);
+
+        let result = challenge.run_first();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+
+        let result = challenge.run_second();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 4);
+    }
+
+    #[test]
+    fn invalid_input() {
+        for input in ["2-4,6", "2-4", "2-x,6-8"] {
+            let challenge = Day4::new(["2-3,4-5", input].into_iter().collect());
+            let err = challenge.run_first().unwrap_err();
+            assert_eq!(err.line().unwrap().number, 2);
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+
+use super::error::*;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::{InputSource, PuzzleInput};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Crate(char);
+
+impl Crate {
+    fn batch(iter: &mut impl Iterator<Item = char>) -> Option<Option<Crate>> {
+        let chars: [char; 3];
+        if let (Some(x), Some(y), Some(z)) = (iter.next(), iter.next(), iter.next()) {
+            chars = [x, y, z];
+        } else {
+            return None;
+        }
+
+        // Skip space between crates
+        iter.next();
+
+        match chars {
+            [' ', ' ', ' '] => Some(None),
+            ['[', c, ']'] => Some(Some(Crate(c))),
+            _ => None,
+        }
+    }
+}
+
+/// The crane doing the moving, which decides the order moved crates end up in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Crane {
+    /// Moves one crate at a time, so the moved crates end up reversed
+    CrateMover9000,
+    /// Moves all the crates at once, keeping their order
+    CrateMover9001,
+}
+
+impl From<ChallengePart> for Crane {
+    fn from(part: ChallengePart) -> Self {
+        match part {
+            ChallengePart::First => Self::CrateMover9000,
+            ChallengePart::Second => Self::CrateMover9001,
+        }
+    }
+}
+
+impl fmt::Display for Crane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CrateMover9000 => write!(f, "CrateMover 9000"),
+            Self::CrateMover9001 => write!(f, "CrateMover 9001"),
+        }
+    }
+}
+
+/// A move between stacks, which are referred to by their index rather than their name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Move {
+    count: usize,
+    src: usize,
+    dst: usize,
+}
+
+impl Move {
+    fn from_commands(line: &str, stacks: &HashMap<&str, usize>) -> Result<Self> {
+        let (mut count, mut src, mut dst) = (None, None, None);
+
+        let mut words = line.split_ascii_whitespace();
+        for _ in 0..3 {
+            match (words.next(), words.next()) {
+                (Some("move"), Some(n)) => {
+                    count = Some(n.parse().map_err(|_| Error::invalid_command(line))?)
+                }
+                (Some("from"), Some(name)) => src = Some(name),
+                (Some("to"), Some(name)) => dst = Some(name),
+                _ => return Err(Error::invalid_command(line)),
+            }
+        }
+
+        let (count, src, dst) = match (count, src, dst) {
+            (Some(count), Some(src), Some(dst)) => (count, src, dst),
+            _ => return Err(Error::invalid_command(line)),
+        };
+        let index = |name| {
+            stacks
+                .get(name)
+                .copied()
+                .ok_or_else(|| Error::missing_data("crate stack"))
+        };
+
+        Ok(Move {
+            count,
+            src: index(src)?,
+            dst: index(dst)?,
+        })
+    }
+
+    /// The move that puts the crates back, for either crane: a CrateMover 9000 reverses them
+    /// again, restoring their order.
+    fn undo(self) -> Move {
+        Move {
+            src: self.dst,
+            dst: self.src,
+            ..self
+        }
+    }
+}
+
+/// Every stack of crates, in the order of the diagram.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stacks {
+    names: Vec<String>,
+    /// Each stack's crates, from the bottom up
+    crates: Vec<Vec<Crate>>,
+}
+
+impl Stacks {
+    /// Carry out a move, taking the crates off the source stack in one go.
+    fn apply(&mut self, r#move: Move, crane: Crane) -> Result<()> {
+        let src = &mut self.crates[r#move.src];
+        let at = src
+            .len()
+            .checked_sub(r#move.count)
+            .ok_or_else(|| Error::missing_data("crate"))?;
+
+        let mut moved = src.split_off(at);
+        if crane == Crane::CrateMover9000 {
+            moved.reverse();
+        }
+        self.crates[r#move.dst].extend(moved);
+
+        Ok(())
+    }
+
+    /// Make sure a stack has at least `count` crates, adding unknown ones at its bottom.
+    fn reserve(&mut self, idx: usize, count: usize) {
+        let stack = &mut self.crates[idx];
+        let missing = count.saturating_sub(stack.len());
+        stack.splice(0..0, std::iter::repeat_n(Crate('?'), missing));
+    }
+
+    fn tops(&self) -> Result<String> {
+        self.crates
+            .iter()
+            .map(|stack| {
+                stack
+                    .last()
+                    .map(|c| c.0)
+                    .ok_or_else(|| Error::missing_data("stack is empty"))
+            })
+            .collect()
+    }
+
+    /// Describe a move in the words of the puzzle, using the names of its stacks. Nothing is
+    /// formatted until the description is displayed, so it's cheap to pass to a log message
+    /// that isn't shown.
+    fn describe<'a>(&'a self, r#move: &'a Move) -> impl fmt::Display + 'a {
+        Description(&self.names, r#move)
+    }
+
+    /// Draw the stacks as the diagram in the puzzle, with the top `count` crates of the `moved`
+    /// stack drawn as `<X>` rather than `[X]`.
+    fn render(&self, moved: Option<(usize, usize)>) -> String {
+        let height = self.crates.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut rows = (0..height)
+            .rev()
+            .map(|level| {
+                self.crates
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, stack)| match (stack.get(level), moved) {
+                        (Some(c), Some((dst, count)))
+                            if dst == idx && level + count >= stack.len() =>
+                        {
+                            format!("<{}>", c.0)
+                        }
+                        (Some(c), _) => format!("[{}]", c.0),
+                        (None, _) => "   ".to_string(),
+                    })
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect_vec();
+
+        rows.push(
+            self.names
+                .iter()
+                .map(|name| format!("{:^3}", name))
+                .join(" ")
+                .trim_end()
+                .to_string(),
+        );
+
+        rows.join("\n")
+    }
+}
+
+struct Description<'a>(&'a [String], &'a Move);
+
+impl fmt::Display for Description<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Description(names, r#move) = self;
+        write!(
+            f,
+            "move {} from {} to {}",
+            r#move.count, names[r#move.src], names[r#move.dst]
+        )
+    }
+}
+
+pub struct Day5 {
+    r#in: PuzzleInput,
+}
+
+impl Day5 {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        Self { r#in }
+    }
+
+    fn run(&self, crane: Crane, ctx: &Context) -> Result<String> {
+        let (mut stacks, moves) = self.setup()?;
+        ctx.debug(format_args!(
+            "{} stacks, {} moves",
+            stacks.names.len(),
+            moves.len()
+        ));
+
+        if ctx.visualizing() {
+            ctx.frame(format_args!("{}, start\n\n{}", crane, stacks.render(None)));
+        }
+
+        let total = moves.len();
+        for (idx, r#move) in moves.into_iter().enumerate() {
+            ctx.trace(stacks.describe(&r#move));
+            stacks.apply(r#move, crane)?;
+
+            if ctx.visualizing() {
+                ctx.frame(format_args!(
+                    "{}, step {}/{}: {}\n\n{}",
+                    crane,
+                    idx + 1,
+                    total,
+                    stacks.describe(&r#move),
+                    stacks.render(Some((r#move.dst, r#move.count)))
+                ));
+            }
+            ctx.progress("move", idx + 1, total);
+        }
+
+        stacks.tops()
+    }
+
+    /// Recover the stacks as they were before any moves were made, by running the moves
+    /// backwards from how the stacks ended up.
+    ///
+    /// `arrangement` is either the final stacks as a diagram, or only the crates left on top of
+    /// each stack, as in the answer. Given only the tops, the crates that can't be known are
+    /// drawn as `[?]`, with no more of them than the moves need.
+    pub fn unstack(&self, part: ChallengePart, arrangement: &str) -> Result<String> {
+        let crane = Crane::from(part);
+        let (start, moves) = self.setup()?;
+
+        let lines = arrangement
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect_vec();
+        let tops_only = lines.len() == 1;
+
+        let mut stacks = if tops_only {
+            let tops = lines[0]
+                .trim()
+                .chars()
+                .map(|c| vec![Crate(c)])
+                .collect_vec();
+            if tops.len() != start.names.len() {
+                return Err(Error::missing_data("crate on top of every stack"));
+            }
+
+            Stacks {
+                names: start.names,
+                crates: tops,
+            }
+        } else {
+            let stacks = Self::setup_stacks(&mut lines.into_iter().enumerate())?;
+            if stacks.names != start.names {
+                return Err(Error::missing_data("stacks named as in the input"));
+            }
+
+            stacks
+        };
+
+        for r#move in moves.into_iter().rev() {
+            if tops_only {
+                stacks.reserve(r#move.dst, r#move.count);
+            }
+            stacks.apply(r#move.undo(), crane)?;
+        }
+
+        Ok(stacks.render(None))
+    }
+
+    /// Read the stacks from the diagram in the first block of the input, and the moves from
+    /// the rest of it.
+    fn setup(&self) -> Result<(Stacks, Vec<Move>)> {
+        let mut blocks = self.r#in.blocks().into_iter();
+
+        let diagram = blocks.next().unwrap_or_default();
+        let stacks = Self::setup_stacks(&mut diagram.into_iter())?;
+        let moves = Self::setup_moves(&mut blocks.flatten(), &stacks)?;
+
+        Ok((stacks, moves))
+    }
+
+    fn setup_stacks<'a>(iter: &mut impl Iterator<Item = (usize, &'a str)>) -> Result<Stacks> {
+        let crate_data = iter
+            .map(|(_, line)| line)
+            .take_while(|line| !line.is_empty())
+            .collect_vec();
+
+        let names = crate_data
+            .last()
+            .ok_or(Error::missing_data("crate stack names"))?
+            .split_whitespace()
+            .map(String::from)
+            .collect_vec();
+        let mut crates = vec![vec![]; names.len()];
+
+        // the diagram is read from the bottom level up, so the stacks are built bottom first
+        for line in crate_data.iter().rev().skip(1) {
+            for (idx, slot) in line.chars().batching(Crate::batch).enumerate() {
+                let Some(c) = slot else {
+                    continue;
+                };
+
+                crates
+                    .get_mut(idx)
+                    .ok_or(Error::missing_data("name for stack"))?
+                    .push(c);
+            }
+        }
+
+        Ok(Stacks { names, crates })
+    }
+
+    fn setup_moves<'a>(
+        iter: &mut impl Iterator<Item = (usize, &'a str)>,
+        stacks: &Stacks,
+    ) -> Result<Vec<Move>> {
+        let index = stacks
+            .names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.as_str(), idx))
+            .collect::<HashMap<_, _>>();
+
+        let moves = iter
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| {
+                Move::from_commands(line, &index).map_err(|err| err.at_line(idx + 1, line))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(moves)
+    }
+}
+
+impl Challenge for Day5 {
+    fn run(&self, part: ChallengePart, ctx: &Context) -> Result<Answer> {
+        self.run(part.into(), ctx).map(Answer::from)
+    }
+}
+
+/// Recover the starting stacks from how they ended up, with the moves taken from the day's
+/// input; see [`Day5::unstack`].
+pub async fn unstack<S: InputSource>(
+    part: ChallengePart,
+    arrangement: &str,
+    input_svc: &S,
+) -> Result<String> {
+    let stacks = match input_svc.get_input(5).await {
+        Ok(r#in) => Day5::new(r#in).unstack(part, arrangement),
+        Err(err) => Err(err.into()),
+    };
+
+    stacks.map_err(|err| err.in_day(5).in_part(part))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::challenge::error::ErrorKind;
+
+    fn example_input() -> PuzzleInput {
+        PuzzleInput::from(include_str!("examples/day5"))
+    }
+
+    #[test]
+    fn example() {
+        let challenge = Day5::new(example_input());
+        let ctx = Context::new(5, ChallengePart::First, 0);
+
+        let result = challenge.run(Crane::CrateMover9000, &ctx);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "CMZ");
+
+        let result = challenge.run(Crane::CrateMover9001, &ctx);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "MCD");
+    }
+
+    #[test]
+    fn unstack() {
+        let input = example_input();
+        let challenge = Day5::new(input.clone());
+
+        let (start, moves) = challenge.setup().unwrap();
+        assert_eq!(
+            start.render(None),
+            concat!("    [D]\n", "[N] [C]\n", "[Z] [M] [P]\n", " 1   2   3")
+        );
+
+        for part in ChallengePart::all() {
+            let mut stacks = start.clone();
+            for r#move in &moves {
+                stacks.apply(*r#move, part.into()).unwrap();
+            }
+
+            let recovered = challenge.unstack(part, &stacks.render(None)).unwrap();
+            assert_eq!(recovered, start.render(None));
+
+            // from only the tops, the recovered stacks end up with the same tops again
+            let tops = stacks.tops().unwrap();
+            let recovered = challenge.unstack(part, &tops).unwrap();
+            let replayed = recovered
+                .lines()
+                .chain([""])
+                .chain(input.lines().skip_while(|line| !line.is_empty()))
+                .collect();
+            let ctx = Context::new(5, part, 0);
+            assert_eq!(Day5::new(replayed).run(part.into(), &ctx).unwrap(), tops);
+        }
+
+        let err = challenge.unstack(ChallengePart::First, "CM").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingDataError(_)));
+    }
+
+    #[test]
+    fn render() {
+        let input = vec![
+            "    [D]    ",
+            "[N] [C]    ",
+            "[Z] [M] [P]",
+            " 1   2   3 ",
+            "",
+        ];
+        let mut iter = input.into_iter().enumerate();
+        let mut stacks = Day5::setup_stacks(&mut iter).unwrap();
+        let moves = Day5::setup_moves(&mut ["move 1 from 2 to 1"].into_iter().enumerate(), &stacks)
+            .unwrap();
+
+        assert_eq!(stacks.describe(&moves[0]).to_string(), "move 1 from 2 to 1");
+        stacks.apply(moves[0], Crane::CrateMover9000).unwrap();
+
+        assert_eq!(
+            stacks.render(Some((0, 1))),
+            concat!("<D>\n", "[N] [C]\n", "[Z] [M] [P]\n", " 1   2   3",)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        for command in ["move x from 1 to 2", "move 1 from 1"] {
+            let challenge = Day5::new(["[A] [B]", " 1   2 ", "", command].into_iter().collect());
+            let err = challenge
+                .run(
+                    Crane::CrateMover9000,
+                    &Context::new(5, ChallengePart::First, 0),
+                )
+                .unwrap_err();
+            assert!(matches!(err.kind, ErrorKind::InvalidCommandError(_)));
+            assert_eq!(err.line().unwrap().number, 4);
+        }
+
+        let challenge = Day5::new(
+            ["[A] [B]", " 1   2 ", "", "move 1 from 1 to 3"]
+                .into_iter()
+                .collect(),
+        );
+        let err = challenge
+            .run(
+                Crane::CrateMover9000,
+                &Context::new(5, ChallengePart::First, 0),
+            )
+            .unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::MissingDataError(_)));
+        assert_eq!(err.line().unwrap().number, 4);
+    }
+
+    #[test]
+    fn bulk_moves() {
+        let mut stacks = Stacks {
+            names: vec!["1".to_string(), "2".to_string()],
+            crates: vec![
+                "ABCDEF".chars().map(Crate).collect(),
+                "XY".chars().map(Crate).collect(),
+            ],
+        };
+
+        stacks
+            .apply(
+                Move {
+                    count: 3,
+                    src: 0,
+                    dst: 1,
+                },
+                Crane::CrateMover9000,
+            )
+            .unwrap();
+        assert_eq!(stacks.tops().unwrap(), "CD");
+
+        stacks
+            .apply(
+                Move {
+                    count: 4,
+                    src: 1,
+                    dst: 0,
+                },
+                Crane::CrateMover9001,
+            )
+            .unwrap();
+        assert_eq!(stacks.tops().unwrap(), "DX");
+
+        // a stack can be moved onto itself
+        stacks
+            .apply(
+                Move {
+                    count: 2,
+                    src: 0,
+                    dst: 0,
+                },
+                Crane::CrateMover9000,
+            )
+            .unwrap();
+        assert_eq!(stacks.tops().unwrap(), "EX");
+
+        let err = stacks.apply(
+            Move {
+                count: 3,
+                src: 1,
+                dst: 0,
+            },
+            Crane::CrateMover9000,
+        );
+        assert!(err.is_err());
+    }
+}
@@ -1,14 +1,16 @@
 use itertools::Itertools;
 
 use super::error::Error;
-use super::{Challenge, ChallengePart, Result};
+use super::{Answer, Challenge, ChallengePart, Context, Result};
 
-pub struct Day6<T: AsRef<str>> {
-    r#in: Vec<T>,
+use crate::input::PuzzleInput;
+
+pub struct Day6 {
+    r#in: PuzzleInput,
 }
 
-impl<T: AsRef<str>> Day6<T> {
-    pub fn new(r#in: Vec<T>) -> Self {
+impl Day6 {
+    pub fn new(r#in: PuzzleInput) -> Self {
         Self { r#in }
     }
 
@@ -18,7 +20,7 @@ impl<T: AsRef<str>> Day6<T> {
             .windows(4)
             .find_position(unique)
             .map(|(pos, _)| pos + 4)
-            .ok_or_else(|| Error::NoSolutionError())?;
+            .ok_or_else(Error::no_solution)?;
 
         Ok(result)
     }
@@ -29,33 +31,34 @@ impl<T: AsRef<str>> Day6<T> {
             .windows(14)
             .find_position(unique)
             .map(|(pos, _)| pos + 14)
-            .ok_or_else(|| Error::NoSolutionError())?;
+            .ok_or_else(Error::no_solution)?;
 
         Ok(result)
     }
 
     fn get_signal(&self) -> Result<Vec<char>> {
-        let mut signal = self
-            .r#in
-            .iter()
-            .map(|line| line.as_ref().chars().collect_vec())
-            .collect_vec();
-
-        if signal.len() > 1 {
-            Err(Error::TooManyLinesError())
-        } else if signal.get(0).is_none() {
-            Err(Error::missing_data("input data"))
-        } else {
-            Ok(signal.swap_remove(0))
+        let signal = self.r#in.raw().trim_end_matches(['\r', '\n']);
+
+        if signal.contains('\n') {
+            return Err(Error::too_many_lines());
+        } else if signal.is_empty() {
+            return Err(Error::missing_data("input data"));
+        }
+
+        // each character is given a bit in a u64, so only lowercase letters are supported
+        if let Some((pos, c)) = signal.chars().find_position(|c| !c.is_ascii_lowercase()) {
+            return Err(Error::invalid_character(c).at_span(1, signal, pos..pos + 1));
         }
+
+        Ok(signal.chars().collect())
     }
 }
 
-impl<T: AsRef<str>> Challenge<T> for Day6<T> {
-    fn run(&self, part: ChallengePart) -> Result<String> {
+impl Challenge for Day6 {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
         match part {
-            ChallengePart::First => self.run_first().map(|res| res.to_string()),
-            ChallengePart::Second => self.run_second().map(|res| res.to_string()),
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
         }
     }
 }
@@ -92,11 +95,13 @@ fn count_bits(bits: u64) -> usize {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::challenge::error::ErrorKind;
 
     #[test]
     fn example() {
+        // only the first example is a block of its own; the rest are given inline in the text
         let table: &[(&str, usize, usize); 5] = &[
-            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            (include_str!("examples/day6"), 7, 19),
             ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
             ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
             ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
@@ -104,7 +109,7 @@ mod test {
         ];
 
         for (input, expected_1, expected_2) in table {
-            let challenge = Day6::new(vec![input]);
+            let challenge = Day6::new(PuzzleInput::from(*input));
 
             let result = challenge.run_first();
             assert!(result.is_ok());
@@ -115,4 +120,12 @@ mod test {
             assert_eq!(&result.unwrap(), expected_2);
         }
     }
+
+    #[test]
+    fn invalid_input() {
+        let challenge = Day6::new(PuzzleInput::from("abcdEfgh"));
+        let err = challenge.run_first().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidCharacterError('E')));
+        assert_eq!(err.line().unwrap().span, 4..5);
+    }
 }
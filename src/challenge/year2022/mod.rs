@@ -0,0 +1,40 @@
+// Each day's `example` test runs against the first example in its puzzle description, as
+// `Input::get_example` extracts it, checked in under `examples/` so that the tests pass offline.
+// The directory can also be given to `--input` to run every day against its example.
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+pub mod day5;
+mod day6;
+
+use super::{error, Answer, Challenge, ChallengePart, Context, DynChallenge, Result};
+
+use error::Error;
+
+use crate::input::InputSource;
+
+/// Every implemented day, with the title of its puzzle.
+pub(super) const DAYS: &[(usize, &str)] = &[
+    (1, "Calorie Counting"),
+    (2, "Rock Paper Scissors"),
+    (3, "Rucksack Reorganization"),
+    (4, "Camp Cleanup"),
+    (5, "Supply Stacks"),
+    (6, "Tuning Trouble"),
+];
+
+pub(super) async fn get_challenge<S: InputSource>(
+    day: usize,
+    input_svc: &S,
+) -> Result<DynChallenge> {
+    match day {
+        day @ 1 => challenge!(day, input_svc => day1::Day1),
+        day @ 2 => challenge!(day, input_svc => day2::Day2),
+        day @ 3 => challenge!(day, input_svc => day3::Day3),
+        day @ 4 => challenge!(day, input_svc => day4::Day4),
+        day @ 5 => challenge!(day, input_svc => day5::Day5),
+        day @ 6 => challenge!(day, input_svc => day6::Day6),
+        day => Err(Error::not_implemented(day)),
+    }
+}
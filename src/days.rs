@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+/// A selection of days given on the command line, as a comma separated list of days and
+/// inclusive ranges of days, such as `1-6` or `1,3,5` or `2,4-6`.
+///
+/// The days are kept sorted and without repeats, so that results come out in day order no
+/// matter how the selection was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days(Vec<usize>);
+
+impl Days {
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = vec![];
+
+        for item in s.split(',').map(str::trim) {
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+                None => (parse_day(item)?, parse_day(item)?),
+            };
+
+            if start == 0 || end > 25 {
+                return Err(format!("`{}` is not between 1 and 25", item));
+            }
+
+            if start > end {
+                return Err(format!("range `{}` ends before it starts", item));
+            }
+
+            days.extend(start..=end);
+        }
+
+        days.sort_unstable();
+        days.dedup();
+
+        Ok(Days(days))
+    }
+}
+
+fn parse_day(s: &str) -> Result<usize, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("`{}` is not a day", s.trim()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let parse = |s: &str| s.parse::<Days>().map(|days| days.as_slice().to_vec());
+
+        assert_eq!(parse("5"), Ok(vec![5]));
+        assert_eq!(parse("1-6"), Ok(vec![1, 2, 3, 4, 5, 6]));
+        assert_eq!(parse("5,1,3"), Ok(vec![1, 3, 5]));
+        assert_eq!(parse("2, 4-6,5"), Ok(vec![2, 4, 5, 6]));
+
+        assert!(parse("").is_err());
+        assert!(parse("6-1").is_err());
+        assert!(parse("1,,2").is_err());
+        assert!(parse("first").is_err());
+        assert!(parse("0-3").is_err());
+        assert!(parse("26").is_err());
+    }
+}
@@ -1,12 +1,136 @@
+use std::fmt;
+use std::path::PathBuf;
+
 use thiserror::Error;
 
-use crate::challenge;
+use crate::challenge::{self, error::ErrorKind};
 use crate::input;
 
+/// Exit codes, so that scripts can tell what kind of failure stopped a run.
+pub const EXIT_FAILURE: u8 = 1;
+/// The same code clap exits with when the arguments can't be parsed
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_INPUT: u8 = 3;
+pub const EXIT_WRONG_ANSWER: u8 = 4;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("error running challenge: {0}")]
+    #[error("error running challenge")]
     ChallengeError(#[from] challenge::error::Error),
-    #[error("input error: {0}")]
+    #[error("error loading input")]
     InputError(#[from] input::error::Error),
+    #[error("{0} challenge(s) failed")]
+    ChallengesFailed(usize),
+    #[error("{0} input(s) could not be loaded")]
+    InputsFailed(usize),
+    #[error("{0} input(s) could not be fetched")]
+    FetchFailed(usize),
+    #[error("{0} answer(s) were wrong")]
+    WrongAnswers(usize),
+    #[error("`{0}` is a single input, so exactly one day must be selected to run it")]
+    InputNeedsOneDay(PathBuf),
+    #[error("error accessing answers in `{0}`")]
+    LedgerError(PathBuf, #[source] std::io::Error),
+    #[error("`{0}` already exists")]
+    DayExists(PathBuf),
+    #[error("couldn't find where to register the day in `{0}`")]
+    RegisterError(PathBuf),
+    #[error("error generating `{0}`")]
+    ScaffoldError(PathBuf, #[source] std::io::Error),
+    #[error("error creating `{0}` for frames")]
+    FramesError(PathBuf, #[source] std::io::Error),
+    #[error("error reading the stacks in `{0}`")]
+    StacksError(PathBuf, #[source] std::io::Error),
+    #[error("error writing results")]
+    OutputError(#[from] std::io::Error),
+}
+
+impl Error {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::ChallengeError(err) => match err.kind {
+                ErrorKind::InvalidDay(_) | ErrorKind::YearNotImplemented(_) => EXIT_USAGE,
+                ErrorKind::InputError(_) => EXIT_INPUT,
+                _ => EXIT_FAILURE,
+            },
+            Error::InputError(input::error::Error::InvalidYear(_))
+            | Error::InputNeedsOneDay(_)
+            | Error::DayExists(_) => EXIT_USAGE,
+            Error::InputError(_)
+            | Error::InputsFailed(_)
+            | Error::FetchFailed(_)
+            | Error::LedgerError(..)
+            | Error::StacksError(..) => EXIT_INPUT,
+            Error::WrongAnswers(_) => EXIT_WRONG_ANSWER,
+            Error::ChallengesFailed(_)
+            | Error::RegisterError(_)
+            | Error::ScaffoldError(..)
+            | Error::FramesError(..)
+            | Error::OutputError(_) => EXIT_FAILURE,
+        }
+    }
+}
+
+/// Displays an error followed by every error in its chain of sources.
+///
+/// The errors are separated by `: ` on one line, or with the alternate flag (`{:#}`), each
+/// source is printed on a line of its own.
+pub struct Chain<'a>(pub &'a (dyn std::error::Error + 'static));
+
+impl fmt::Display for Chain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut source = self.0.source();
+        while let Some(err) = source {
+            if f.alternate() {
+                write!(f, "\n  caused by: {}", err)?;
+            } else {
+                write!(f, ": {}", err)?;
+            }
+
+            source = err.source();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chain() {
+        let missing = input::error::Error::MissingInputError(5);
+        let err = Error::from(challenge::error::Error::from(missing).in_day(5));
+
+        assert_eq!(
+            Chain(&err).to_string(),
+            "error running challenge: error loading input: no input for day `5`"
+        );
+        assert_eq!(
+            format!("{:#}", Chain(&err)),
+            concat!(
+                "error running challenge\n",
+                "  caused by: error loading input\n",
+                "  caused by: no input for day `5`",
+            )
+        );
+        assert_eq!(err.exit_code(), EXIT_INPUT);
+
+        let err = Error::from(challenge::error::Error::invalid_day(26));
+        assert_eq!(
+            Chain(&err).to_string(),
+            "error running challenge: invalid day `26`"
+        );
+        assert_eq!(err.exit_code(), EXIT_USAGE);
+
+        assert_eq!(Error::WrongAnswers(1).exit_code(), EXIT_WRONG_ANSWER);
+        assert_eq!(
+            Error::InputNeedsOneDay(PathBuf::from("input.txt")).exit_code(),
+            EXIT_USAGE
+        );
+    }
 }
@@ -0,0 +1,88 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight US Eastern Standard Time, which is 05:00 UTC
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// The number of puzzles released in a year; the calendar was shortened from 2025 onwards.
+pub fn days_in_year(year: usize) -> usize {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// The instant a day's puzzle (and input) becomes available.
+pub fn unlock_time(year: usize, day: usize) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    let secs = days as u64 * 24 * 60 * 60 + UNLOCK_HOUR_UTC * 60 * 60;
+
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+pub fn is_released(year: usize, day: usize, now: SystemTime) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// All days of the year whose puzzles have been released by `now`.
+pub fn released_days(year: usize, now: SystemTime) -> Vec<usize> {
+    (1..=days_in_year(year))
+        .filter(|day| is_released(year, *day, now))
+        .collect()
+}
+
+/// How long until a day's puzzle unlocks, or `None` if it already has.
+pub fn time_until_unlock(year: usize, day: usize, now: SystemTime) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(now)
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Format a duration as `HH:MM:SS`, with as many hours as needed.
+pub fn format_hms(duration: &Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Count the days between the unix epoch and a date in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unlock() {
+        // 2022-12-01T05:00:00Z
+        let unlock = UNIX_EPOCH + Duration::from_secs(1669870800);
+        assert_eq!(unlock_time(2022, 1), unlock);
+
+        let second = Duration::from_secs(1);
+        assert!(!is_released(2022, 1, unlock - second));
+        assert!(is_released(2022, 1, unlock));
+
+        assert_eq!(released_days(2022, unlock + second), vec![1]);
+        assert_eq!(released_days(2022, unlock - second), Vec::<usize>::new());
+        assert_eq!(released_days(2025, unlock_time(2026, 1)).len(), 12);
+
+        let remaining = Duration::from_secs(24 * 3600 + 61);
+        assert_eq!(
+            time_until_unlock(2022, 2, unlock - second * 61),
+            Some(remaining)
+        );
+        assert_eq!(time_until_unlock(2022, 1, unlock), None);
+        assert_eq!(format_hms(&remaining), "24:01:01");
+    }
+}
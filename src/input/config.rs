@@ -0,0 +1,144 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::error::Error;
+use super::{read_file, Result};
+
+const SESSION_ENV: &str = "AOC_SESSION";
+const CACHE_DIR_ENV: &str = "AOC_CACHE_DIR";
+const SESSION_FILE: &str = ".session";
+const DEFAULT_CACHE_DIR: &str = "./input";
+const BASE_URI: &str = "https://adventofcode.com";
+
+/// AoC asks automated tools to identify themselves and give a way to contact their author
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/carsonmyers/advent2022 by carson@myers.se)"
+);
+
+/// Options controlling where inputs are cached and how they are downloaded.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub year: usize,
+    /// A session token given explicitly, taking precedence over any other source
+    pub session: Option<String>,
+    /// Where inputs are cached; falls back to `$AOC_CACHE_DIR`, then `./input`
+    pub cache_dir: Option<PathBuf>,
+    /// Never make HTTP requests; inputs that aren't cached are an error
+    pub offline: bool,
+    /// Wait for puzzles that haven't unlocked yet instead of failing
+    pub wait: bool,
+    /// Root of the Advent of Code site, which puzzle URLs are built from
+    pub base_url: String,
+    pub user_agent: String,
+    /// The minimum time between the start of two requests
+    pub request_interval: Duration,
+    /// How many times a request that failed for a transient reason is retried
+    pub max_retries: u32,
+    /// How long to wait before the first retry; the wait doubles with each further retry
+    pub retry_backoff: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2022,
+            session: None,
+            cache_dir: None,
+            offline: false,
+            wait: false,
+            base_url: BASE_URI.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            request_interval: Duration::from_secs(1),
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Config {
+    pub(super) fn resolve_cache_dir(&self) -> PathBuf {
+        self.cache_dir
+            .clone()
+            .or_else(|| env::var_os(CACHE_DIR_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR))
+    }
+
+    /// Find the session token, trying in order: the explicit token, `$AOC_SESSION`, a
+    /// `.session` file in the working directory, and `aoc/session` in the XDG config
+    /// directory.
+    pub(super) async fn resolve_session(&self) -> Result<String> {
+        let explicit = self.session.clone().or_else(|| env::var(SESSION_ENV).ok());
+
+        resolve_session(explicit, &session_files()).await
+    }
+}
+
+async fn resolve_session(explicit: Option<String>, files: &[PathBuf]) -> Result<String> {
+    if let Some(session) = explicit.as_deref().map(str::trim) {
+        if !session.is_empty() {
+            return Ok(session.to_string());
+        }
+    }
+
+    for path in files {
+        if !path.exists() {
+            continue;
+        }
+
+        let session = read_file(path).await?;
+        let session = session.trim();
+        if !session.is_empty() {
+            return Ok(session.to_string());
+        }
+    }
+
+    Err(Error::MissingSession(files.to_vec()))
+}
+
+fn session_files() -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(SESSION_FILE)];
+
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    if let Some(config_home) = config_home {
+        files.push(config_home.join("aoc").join("session"));
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn session_precedence() {
+        let dir = env::temp_dir().join(format!("aoc-session-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let empty = dir.join("empty");
+        let file = dir.join("session");
+        std::fs::write(&empty, "\n").unwrap();
+        std::fs::write(&file, "from-file\n").unwrap();
+
+        let files = vec![dir.join("missing"), empty, file];
+
+        let session = resolve_session(Some(" explicit ".to_string()), &files).await;
+        assert_eq!(session.unwrap(), "explicit");
+
+        let session = resolve_session(None, &files).await;
+        assert_eq!(session.unwrap(), "from-file");
+
+        let session = resolve_session(Some(String::new()), &files[..2]).await;
+        assert!(matches!(session, Err(Error::MissingSession(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
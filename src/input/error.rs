@@ -1,19 +1,58 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use thiserror::Error;
 
+use super::calendar;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("http error: {0}")]
+    #[error("http request failed")]
     HttpError(#[from] reqwest::Error),
     #[error("path error")]
     PathError,
-    #[error("parse error: {0}")]
+    #[error("invalid url")]
     ParseError(#[from] url::ParseError),
-    #[error("io error: {0}")]
+    #[error("io error")]
     IOError(#[from] std::io::Error),
     #[error("file `{0}` is not utf8")]
     Utf8Error(PathBuf),
+    #[error("no session token found; pass --session, set AOC_SESSION, or create one of {}", display_paths(.0))]
+    MissingSession(Vec<PathBuf>),
+    #[error("input `{0}` is not cached and can't be downloaded in offline mode")]
+    NotCached(PathBuf),
+    #[error("day `{0}` unlocks in {}", calendar::format_hms(.1))]
+    NotUnlockedError(usize, Duration),
+    #[error("session token was rejected; it may have expired")]
+    AuthError,
+    #[error("the input for day `{0}` is not available yet")]
+    NotAvailableError(usize),
+    #[error("too many requests")]
+    RateLimitedError,
+    #[error("server error (status {0})")]
+    ServerError(u16),
+    #[error("unexpected response (status {0}) instead of puzzle input")]
+    UnexpectedResponseError(u16),
+    #[error("no input for day `{0}`")]
+    MissingInputError(usize),
+    #[error("day `{0}` has no example `{1}`")]
+    ExampleNotFoundError(usize, String),
+    #[error("the description of day `{0}` has no title")]
+    MissingTitleError(usize),
+    #[error("invalid year `{0}`")]
+    InvalidYear(usize),
+}
+
+impl Error {
+    /// Whether the request that failed with this error may succeed if it's tried again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::HttpError(err) => err.is_timeout() || err.is_connect(),
+            Error::RateLimitedError | Error::ServerError(_) => true,
+            _ => false,
+        }
+    }
 }
 
 impl From<()> for Error {
@@ -21,3 +60,11 @@ impl From<()> for Error {
         Error::PathError
     }
 }
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("`{}`", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
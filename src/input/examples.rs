@@ -0,0 +1,154 @@
+/// A block of preformatted text from a puzzle description, most of which are example inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// The 1-based position of the block within the description
+    pub name: String,
+    pub text: String,
+}
+
+const OPEN: &str = "<pre><code>";
+const CLOSE: &str = "</code></pre>";
+
+/// Extract the text of every `<pre><code>` block in a puzzle description.
+///
+/// Puzzle pages are simple enough that a full HTML parser isn't needed: the blocks are never
+/// nested, and the only markup inside them is emphasis, which is dropped.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let mut examples = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(OPEN) {
+        rest = &rest[start + OPEN.len()..];
+        let end = match rest.find(CLOSE) {
+            Some(end) => end,
+            None => break,
+        };
+
+        examples.push(Example {
+            name: (examples.len() + 1).to_string(),
+            text: decode_entities(&strip_tags(&rest[..end])),
+        });
+
+        rest = &rest[end + CLOSE.len()..];
+    }
+
+    examples
+}
+
+/// Extract the title of a puzzle from its description, which is the first heading on the page,
+/// written like `--- Day 5: Supply Stacks ---`.
+pub fn extract_title(html: &str) -> Option<String> {
+    let start = html.find("<h2")?;
+    let rest = &html[start..];
+    let heading = &rest[rest.find('>')? + 1..rest.find("</h2>")?];
+
+    let heading = decode_entities(&strip_tags(heading));
+    let title = heading.trim().trim_matches('-').trim();
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+
+    Some(title.to_string()).filter(|title| !title.is_empty())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end))
+            .and_then(|(name, end)| decode_entity(name).map(|c| (c, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        name => {
+            let code = if let Some(hex) = name.strip_prefix("#x") {
+                u32::from_str_radix(hex, 16).ok()?
+            } else {
+                name.strip_prefix('#')?.parse().ok()?
+            };
+
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let html = concat!(
+            "<article><p>For example:</p>\n",
+            "<pre><code>    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n",
+            "move 1 from 2 to 1\n</code></pre>\n",
+            "<p>Then <code>inline</code> code</p>\n",
+            "<pre><code><em>a</em> &lt;-&gt; b &amp; c&#39;s &#x41;\n</code></pre>",
+            "<pre><code>unterminated",
+        );
+
+        let examples = extract_examples(html);
+        assert_eq!(examples.len(), 2);
+
+        assert_eq!(examples[0].name, "1");
+        assert_eq!(
+            examples[0].text,
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\n"
+        );
+
+        assert_eq!(examples[1].name, "2");
+        assert_eq!(examples[1].text, "a <-> b & c's A\n");
+    }
+
+    #[test]
+    fn title() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>"#;
+        assert_eq!(extract_title(html), Some("Supply Stacks".to_string()));
+
+        let html = "<h2 id=\"part2\">--- Part Two ---</h2>";
+        assert_eq!(extract_title(html), Some("Part Two".to_string()));
+
+        assert_eq!(extract_title("<p>no heading</p>"), None);
+    }
+}
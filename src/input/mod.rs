@@ -1,70 +1,383 @@
+pub mod calendar;
+mod config;
 pub mod error;
+mod examples;
+mod puzzle;
+mod source;
+mod throttle;
 
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
-use reqwest::{self, Url};
+use reqwest::{self, StatusCode, Url};
 use tokio::{
-    fs::File,
+    fs::{self, File},
     io::{AsyncReadExt, AsyncWriteExt},
+    sync::OnceCell,
 };
 
+pub use config::{Config, DEFAULT_USER_AGENT};
 use error::Error;
+pub use puzzle::PuzzleInput;
+pub use source::{DirectorySource, ExampleSource, InputSource, MemorySource};
+use throttle::Throttle;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-const BASE_URI: &'static str = "https://adventofcode.com/2022";
-const SESSION_FILE: &'static str = ".session";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+/// The first year Advent of Code ran
+pub const FIRST_YEAR: usize = 2015;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// The input was already cached, so nothing was downloaded
+    Cached,
+    Downloaded,
+}
 
 pub struct Input {
     client: reqwest::Client,
-    session: String,
+    config: Config,
+    cache_dir: PathBuf,
+    throttle: Throttle,
+    /// Only needed to download inputs, so it isn't looked up until the first download
+    session: OnceCell<String>,
 }
 
 impl Input {
-    pub async fn new() -> Result<Self> {
-        let session = read_file(SESSION_FILE).await?;
-        let client = reqwest::Client::new();
+    pub fn new(config: Config) -> Result<Self> {
+        if config.year < FIRST_YEAR {
+            return Err(Error::InvalidYear(config.year));
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent(config.user_agent.as_str())
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        let cache_dir = config.resolve_cache_dir();
+        let throttle = Throttle::new(config.request_interval);
+
+        Ok(Input {
+            client,
+            config,
+            cache_dir,
+            throttle,
+            session: OnceCell::new(),
+        })
+    }
+
+    pub fn year(&self) -> usize {
+        self.config.year
+    }
 
-        Ok(Input { client, session })
+    async fn session(&self) -> Result<&str> {
+        let session = self
+            .session
+            .get_or_try_init(|| self.config.resolve_session())
+            .await?;
+
+        Ok(session)
     }
 
-    pub async fn get_input(&self, day: usize) -> Result<Vec<String>> {
-        if !input_path(day).as_path().exists() {
+    pub async fn get_input(&self, day: usize) -> Result<PuzzleInput> {
+        let path = self.cached_path(day);
+        if !path.exists() {
             self.download_input(day).await?;
         }
 
-        get_input_from_file(day).await
+        get_input_from_file(&path).await
+    }
+
+    /// Make sure a day's input is cached, downloading it if it isn't.
+    pub async fn fetch(&self, day: usize) -> Result<FetchStatus> {
+        if self.is_cached(day) {
+            return Ok(FetchStatus::Cached);
+        }
+
+        self.download_input(day).await?;
+        Ok(FetchStatus::Downloaded)
+    }
+
+    /// The days of the configured year whose inputs can be fetched: those released so far, and
+    /// in wait mode the next one to unlock as well.
+    pub fn fetchable_days(&self) -> Vec<usize> {
+        let mut days = calendar::released_days(self.year(), SystemTime::now());
+
+        // days unlock in order, so the next one is right after the released ones
+        let next = days.len() + 1;
+        if self.config.wait && next <= calendar::days_in_year(self.year()) {
+            days.push(next);
+        }
+
+        days
+    }
+
+    pub fn is_cached(&self, day: usize) -> bool {
+        self.cached_path(day).exists()
+    }
+
+    /// Where the answers known to be correct for the configured year are kept.
+    pub fn answers_path(&self) -> PathBuf {
+        let mut path = self.cache_dir.clone();
+        path.push(self.year().to_string());
+        path.push("answers");
+
+        path
+    }
+
+    /// Find where a day's input is cached, preferring inputs cached before the cache was split
+    /// up by year if they exist.
+    pub fn cached_path(&self, day: usize) -> PathBuf {
+        let legacy = self.legacy_input_path(day);
+        if self.year() == 2022 && legacy.exists() {
+            legacy
+        } else {
+            self.input_path(day)
+        }
+    }
+
+    fn input_path(&self, day: usize) -> PathBuf {
+        let mut path = self.cache_dir.clone();
+        path.push(self.year().to_string());
+        path.push(format!("day{:02}", day));
+
+        path
+    }
+
+    fn legacy_input_path(&self, day: usize) -> PathBuf {
+        let mut path = self.cache_dir.clone();
+        path.push(format!("day{}", day));
+
+        path
     }
 
     pub async fn download_input(&self, day: usize) -> Result<()> {
-        let mut url = Url::parse(BASE_URI)?;
-        url.path_segments_mut()?
-            .push("day")
-            .push(&day.to_string())
-            .push("input");
-
-        let response = self
-            .client
-            .get(url)
-            .header("Cookie", format!("session={}", self.session))
-            .send()
-            .await?
-            .bytes()
+        if self.config.offline {
+            return Err(Error::NotCached(self.input_path(day)));
+        }
+
+        self.wait_for_unlock(day).await?;
+
+        let url = self.puzzle_url(day, Some("input"))?;
+        let session = self.session().await?;
+        let body = self
+            .request(url, Some(session), |status, body| {
+                check_input(day, status, body)
+            })
             .await?;
 
-        let mut file = File::create(input_path(day).as_path()).await?;
-        file.write_all(response.as_ref()).await?;
+        write_atomic(&self.input_path(day), body.as_ref()).await
+    }
+
+    /// Get the HTML page describing a day's puzzle, downloading it if it isn't cached.
+    ///
+    /// The page only includes the second part of the puzzle once the first has been solved, so
+    /// it may be worth deleting the cached page to download it again.
+    pub async fn get_description(&self, day: usize) -> Result<String> {
+        let path = self.description_path(day);
+        if !path.exists() {
+            self.download_description(day).await?;
+        }
+
+        read_file(&path).await
+    }
+
+    /// Get one of the examples from a day's puzzle description, named by its 1-based position
+    /// within the description.
+    pub async fn get_example(&self, day: usize, name: &str) -> Result<PuzzleInput> {
+        let description = self.get_description(day).await?;
+        let example = examples::extract_examples(&description)
+            .into_iter()
+            .find(|example| example.name == name)
+            .ok_or_else(|| Error::ExampleNotFoundError(day, name.to_string()))?;
+
+        Ok(PuzzleInput::new(example.text))
+    }
+
+    /// Get the title of a day's puzzle from its description.
+    pub async fn get_title(&self, day: usize) -> Result<String> {
+        let description = self.get_description(day).await?;
+        examples::extract_title(&description).ok_or(Error::MissingTitleError(day))
+    }
+
+    async fn download_description(&self, day: usize) -> Result<()> {
+        if self.config.offline {
+            return Err(Error::NotCached(self.description_path(day)));
+        }
 
+        self.wait_for_unlock(day).await?;
+
+        // the description is public, but it only includes the second part when logged in
+        let url = self.puzzle_url(day, None)?;
+        let session = self.session().await.ok();
+        let body = self
+            .request(url, session, |status, _| check_status(day, status))
+            .await?;
+
+        write_atomic(&self.description_path(day), body.as_ref()).await
+    }
+
+    fn puzzle_url(&self, day: usize, resource: Option<&str>) -> Result<Url> {
+        let mut url = Url::parse(&self.config.base_url)?;
+        {
+            let mut segments = url.path_segments_mut()?;
+            segments
+                .pop_if_empty()
+                .push(&self.year().to_string())
+                .push("day")
+                .push(&day.to_string());
+
+            if let Some(resource) = resource {
+                segments.push(resource);
+            }
+        }
+
+        Ok(url)
+    }
+
+    fn description_path(&self, day: usize) -> PathBuf {
+        let mut path = self.cache_dir.clone();
+        path.push(self.year().to_string());
+        path.push(format!("day{:02}.html", day));
+
+        path
+    }
+
+    /// Make sure a day has unlocked before requesting its input, sleeping until it does in
+    /// wait mode.
+    async fn wait_for_unlock(&self, day: usize) -> Result<()> {
+        let remaining = match calendar::time_until_unlock(self.year(), day, SystemTime::now()) {
+            Some(remaining) => remaining,
+            None => return Ok(()),
+        };
+
+        if !self.config.wait {
+            return Err(Error::NotUnlockedError(day, remaining));
+        }
+
+        // leave a little slack for the difference between our clock and the server's
+        tokio::time::sleep(remaining + UNLOCK_GRACE).await;
         Ok(())
     }
+
+    /// Make a throttled GET request, retrying with exponential backoff if it fails for a
+    /// transient reason. `check` decides whether a response is acceptable.
+    async fn request(
+        &self,
+        url: Url,
+        session: Option<&str>,
+        check: impl Fn(StatusCode, &[u8]) -> Result<()>,
+    ) -> Result<Vec<u8>> {
+        let mut backoff = self.config.retry_backoff;
+        let mut retries = 0;
+
+        loop {
+            match self.request_once(url.clone(), session, &check).await {
+                Err(err) if err.is_transient() && retries < self.config.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn request_once(
+        &self,
+        url: Url,
+        session: Option<&str>,
+        check: impl Fn(StatusCode, &[u8]) -> Result<()>,
+    ) -> Result<Vec<u8>> {
+        self.throttle.wait().await;
+
+        let mut request = self.client.get(url);
+        if let Some(session) = session {
+            request = request.header("Cookie", format!("session={}", session));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+        check(status, body.as_ref())?;
+
+        Ok(body.to_vec())
+    }
+}
+
+/// Messages AoC responds with instead of an input. They may come with a non-success status,
+/// but are checked regardless so they never end up cached as an input.
+const AUTH_MESSAGE: &str = "Puzzle inputs differ by user.";
+const NOT_AVAILABLE_MESSAGE: &str =
+    "Please don't repeatedly request this endpoint before it unlocks!";
+
+/// Make sure a response actually contains a puzzle input.
+fn check_input(day: usize, status: StatusCode, body: &[u8]) -> Result<()> {
+    let text = String::from_utf8_lossy(body);
+    let text = text.trim_start();
+
+    if text.starts_with(AUTH_MESSAGE) {
+        return Err(Error::AuthError);
+    }
+
+    if text.starts_with(NOT_AVAILABLE_MESSAGE) {
+        return Err(Error::NotAvailableError(day));
+    }
+
+    check_status(day, status)?;
+
+    let looks_like_html = text.starts_with("<!DOCTYPE") || text.starts_with("<html");
+    if looks_like_html || body.is_empty() {
+        return Err(Error::UnexpectedResponseError(status.as_u16()));
+    }
+
+    Ok(())
 }
 
-async fn get_input_from_file(day: usize) -> Result<Vec<String>> {
-    Ok(read_file(input_path(day).as_path())
-        .await?
-        .lines()
-        .map(String::from)
-        .collect())
+fn check_status(day: usize, status: StatusCode) -> Result<()> {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(Error::AuthError)
+        }
+        StatusCode::NOT_FOUND => Err(Error::NotAvailableError(day)),
+        StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimitedError),
+        status if status.is_server_error() => Err(Error::ServerError(status.as_u16())),
+        status if !status.is_success() => Err(Error::UnexpectedResponseError(status.as_u16())),
+        _ => Ok(()),
+    }
+}
+
+/// Write to a temporary file next to `path` and then move it into place, so an interrupted
+/// write never leaves a partial input in the cache.
+async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).await?;
+    }
+
+    let mut tmp_name = path.file_name().ok_or(Error::PathError)?.to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = File::create(&tmp_path).await?;
+    let written = async {
+        file.write_all(contents).await?;
+        file.sync_all().await
+    }
+    .await;
+
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(err.into());
+    }
+
+    fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+async fn get_input_from_file(path: &Path) -> Result<PuzzleInput> {
+    Ok(PuzzleInput::new(read_file(path).await?))
 }
 
 async fn read_file<T: AsRef<Path>>(path: T) -> Result<String> {
@@ -78,11 +391,191 @@ async fn read_file<T: AsRef<Path>>(path: T) -> Result<String> {
     Ok(contents)
 }
 
-fn input_path(day: usize) -> PathBuf {
-    let mut path = PathBuf::new();
-    path.push(".");
-    path.push("input");
-    path.push(format!("day{}", day));
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serve each response to one connection, in order, recording the requests received.
+    async fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(request).unwrap().to_lowercase());
+
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
 
-    path
+        (base_url, requests)
+    }
+
+    fn mock_input(base_url: String, name: &str) -> Input {
+        let cache_dir =
+            std::env::temp_dir().join(format!("aoc-mock-test-{}-{}", name, std::process::id()));
+
+        Input::new(Config {
+            session: Some("abc123".to_string()),
+            cache_dir: Some(cache_dir),
+            base_url,
+            user_agent: "mock-agent".to_string(),
+            request_interval: Duration::from_millis(30),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn download_with_retry() {
+        let (base_url, requests) = mock_server(vec![(503, "busy"), (200, "1000\n2000\n")]).await;
+        let input = mock_input(base_url, "retry");
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(input.fetch(1).await.unwrap(), FetchStatus::Downloaded);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(input.get_input(1).await.unwrap().raw(), "1000\n2000\n");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("get /2022/day/1/input "));
+        assert!(requests[1].contains("user-agent: mock-agent\r\n"));
+        assert!(requests[1].contains("cookie: session=abc123\r\n"));
+
+        std::fs::remove_dir_all(&input.cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn examples() {
+        let page = "<html><pre><code>A Y\nB X\nC Z\n</code></pre></html>";
+        let (base_url, requests) = mock_server(vec![(200, page)]).await;
+        let input = mock_input(base_url, "examples");
+
+        assert_eq!(
+            input.get_example(2, "1").await.unwrap().raw(),
+            "A Y\nB X\nC Z\n"
+        );
+
+        // the description is only downloaded once
+        let missing = input.get_example(2, "2").await;
+        assert!(matches!(missing, Err(Error::ExampleNotFoundError(2, _))));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("get /2022/day/2 "));
+
+        std::fs::remove_dir_all(&input.cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn download_failures() {
+        let (base_url, requests) = mock_server(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (500, "oops"),
+            (502, "oops"),
+            (503, "oops"),
+        ])
+        .await;
+        let input = mock_input(base_url, "failures");
+
+        // authentication failures aren't retried
+        let auth = input.fetch(1).await;
+        assert!(matches!(auth, Err(Error::AuthError)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // server errors are retried until the retries run out
+        let server = input.fetch(1).await;
+        assert!(matches!(server, Err(Error::ServerError(503))));
+        assert_eq!(requests.lock().unwrap().len(), 4);
+
+        assert!(!input.input_path(1).exists());
+    }
+
+    #[test]
+    fn fetchable_days() {
+        let input = |year, wait| {
+            Input::new(Config {
+                year,
+                wait,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(
+            input(2022, true).fetchable_days(),
+            (1..=25).collect::<Vec<_>>()
+        );
+        assert!(input(2099, false).fetchable_days().is_empty());
+        assert_eq!(input(2099, true).fetchable_days(), vec![1]);
+    }
+
+    #[test]
+    fn responses() {
+        let ok = check_input(1, StatusCode::OK, b"1000\n2000\n");
+        assert!(ok.is_ok());
+
+        let auth = format!("{}\n", AUTH_MESSAGE);
+        let auth = check_input(1, StatusCode::BAD_REQUEST, auth.as_bytes());
+        assert!(matches!(auth, Err(Error::AuthError)));
+
+        let early = format!("{}\n", NOT_AVAILABLE_MESSAGE);
+        let early = check_input(7, StatusCode::NOT_FOUND, early.as_bytes());
+        assert!(matches!(early, Err(Error::NotAvailableError(7))));
+
+        let early = check_input(7, StatusCode::OK, NOT_AVAILABLE_MESSAGE.as_bytes());
+        assert!(matches!(early, Err(Error::NotAvailableError(7))));
+
+        let server = check_input(1, StatusCode::BAD_GATEWAY, b"<html></html>");
+        assert!(matches!(server, Err(Error::ServerError(502))));
+
+        let page = check_input(1, StatusCode::OK, b"<!DOCTYPE html>\n<html>");
+        assert!(matches!(page, Err(Error::UnexpectedResponseError(200))));
+    }
+
+    #[tokio::test]
+    async fn atomic_write() {
+        let dir = std::env::temp_dir().join(format!("aoc-write-test-{}", std::process::id()));
+        let path = dir.join("2022").join("day01");
+
+        write_atomic(&path, b"first").await.unwrap();
+        write_atomic(&path, b"second").await.unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"second");
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,199 @@
+use std::fmt;
+
+/// A day's puzzle input, exactly as it was downloaded or read.
+///
+/// Nothing is stripped or normalised, so days that care about trailing whitespace, blank lines
+/// or line endings can look at the raw text, while the other views cover the common shapes
+/// puzzle inputs come in.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PuzzleInput {
+    raw: String,
+}
+
+impl PuzzleInput {
+    pub fn new<T: Into<String>>(raw: T) -> Self {
+        PuzzleInput { raw: raw.into() }
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    // no day reads bytes yet
+    #[allow(dead_code)]
+    pub fn bytes(&self) -> &[u8] {
+        self.raw.as_bytes()
+    }
+
+    /// The lines of the input without their `\n` or `\r\n` terminators. Blank lines, including
+    /// trailing ones, are kept.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.raw.lines()
+    }
+
+    /// Groups of lines separated by one or more blank lines. Each line comes with its 0-based
+    /// index in the input, so that errors can still point at the line they were found on.
+    pub fn blocks(&self) -> Vec<Vec<(usize, &str)>> {
+        let mut blocks = vec![];
+        let mut block = vec![];
+
+        for (idx, line) in self.lines().enumerate() {
+            if line.trim().is_empty() {
+                if !block.is_empty() {
+                    blocks.push(std::mem::take(&mut block));
+                }
+            } else {
+                block.push((idx, line));
+            }
+        }
+
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+
+        blocks
+    }
+
+    /// The input as a grid of characters, ignoring any trailing blank lines. Rows shorter than
+    /// the longest one are padded with spaces.
+    // no day is laid out as a grid yet
+    #[allow(dead_code)]
+    pub fn grid(&self) -> Grid {
+        let mut rows = self.lines().collect::<Vec<_>>();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            let len = cells.len();
+            cells.extend(row.chars());
+            cells.resize(len + width, ' ');
+        }
+
+        Grid {
+            width,
+            height: rows.len(),
+            cells,
+        }
+    }
+}
+
+impl From<String> for PuzzleInput {
+    fn from(raw: String) -> Self {
+        PuzzleInput::new(raw)
+    }
+}
+
+impl From<&str> for PuzzleInput {
+    fn from(raw: &str) -> Self {
+        PuzzleInput::new(raw)
+    }
+}
+
+/// Join lines back into an input, ending each of them with a line break as in a file.
+impl<T: AsRef<str>> FromIterator<T> for PuzzleInput {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let raw = iter.into_iter().fold(String::new(), |mut raw, line| {
+            raw.push_str(line.as_ref());
+            raw.push('\n');
+            raw
+        });
+
+        PuzzleInput::new(raw)
+    }
+}
+
+impl fmt::Display for PuzzleInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// A rectangular grid of characters, indexed by column and row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+}
+
+// no day is laid out as a grid yet
+#[allow(dead_code)]
+impl Grid {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.cells.get(y * self.width + x).copied()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        // chunks panics on a chunk size of 0, which an empty grid would have
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn views() {
+        let input = PuzzleInput::new("    [D]    \r\n[N] [C]    \r\n\r\n\nmove 1 from 2 to 1\n\n");
+
+        assert!(input.raw().contains('\r'));
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            vec![
+                "    [D]    ",
+                "[N] [C]    ",
+                "",
+                "",
+                "move 1 from 2 to 1",
+                ""
+            ]
+        );
+        assert_eq!(
+            input.blocks(),
+            vec![
+                vec![(0, "    [D]    "), (1, "[N] [C]    ")],
+                vec![(4, "move 1 from 2 to 1")]
+            ]
+        );
+
+        let joined = ["a", "", "b"].into_iter().collect::<PuzzleInput>();
+        assert_eq!(joined.raw(), "a\n\nb\n");
+        assert_eq!(joined.lines().collect::<Vec<_>>(), vec!["a", "", "b"]);
+    }
+    #[test]
+    fn grid() {
+        let grid = PuzzleInput::new("ab\nc\n\n").grid();
+
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 0), Some('b'));
+        assert_eq!(grid.get(1, 1), Some(' '));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b'][..], &['c', ' '][..]]
+        );
+
+        assert_eq!(PuzzleInput::new("").grid().rows().count(), 0);
+    }
+}
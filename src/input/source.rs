@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use tokio::io::AsyncReadExt;
+
+use super::error::Error;
+use super::{get_input_from_file, Input, PuzzleInput, Result};
+
+/// Somewhere a day's puzzle input can be loaded from.
+pub trait InputSource: Sync {
+    fn get_input(&self, day: usize) -> impl Future<Output = Result<PuzzleInput>> + Send;
+}
+
+/// Inputs cached from adventofcode.com, downloading any that aren't cached yet.
+impl InputSource for Input {
+    async fn get_input(&self, day: usize) -> Result<PuzzleInput> {
+        Input::get_input(self, day).await
+    }
+}
+
+/// Inputs stored as files named `dayNN` or `dayN` in a directory, without anything being
+/// downloaded.
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    dir: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        DirectorySource { dir: dir.into() }
+    }
+}
+
+impl InputSource for DirectorySource {
+    async fn get_input(&self, day: usize) -> Result<PuzzleInput> {
+        let path = [format!("day{:02}", day), format!("day{}", day)]
+            .into_iter()
+            .map(|name| self.dir.join(name))
+            .find(|path| path.exists())
+            .ok_or(Error::MissingInputError(day))?;
+
+        get_input_from_file(&path).await
+    }
+}
+
+/// Inputs held in memory, such as one given on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    inputs: HashMap<usize, PuzzleInput>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_input<T: Into<PuzzleInput>>(mut self, day: usize, input: T) -> Self {
+        self.inputs.insert(day, input.into());
+        self
+    }
+
+    /// Read a single day's input from a file, or from stdin if the path is `-`.
+    pub async fn from_file(day: usize, path: &Path) -> Result<Self> {
+        if path != Path::new("-") {
+            let input = get_input_from_file(path).await?;
+            return Ok(Self::new().with_input(day, input));
+        }
+
+        let mut raw_contents = vec![];
+        tokio::io::stdin().read_to_end(&mut raw_contents).await?;
+
+        let contents =
+            String::from_utf8(raw_contents).map_err(|_| Error::Utf8Error(path.to_path_buf()))?;
+
+        Ok(Self::new().with_input(day, contents))
+    }
+}
+
+impl InputSource for MemorySource {
+    async fn get_input(&self, day: usize) -> Result<PuzzleInput> {
+        self.inputs
+            .get(&day)
+            .cloned()
+            .ok_or(Error::MissingInputError(day))
+    }
+}
+
+/// The examples embedded in each day's puzzle description, picked out by name.
+pub struct ExampleSource<'a> {
+    input: &'a Input,
+    name: String,
+}
+
+impl<'a> ExampleSource<'a> {
+    pub fn new(input: &'a Input, name: &str) -> Self {
+        ExampleSource {
+            input,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl InputSource for ExampleSource<'_> {
+    async fn get_input(&self, day: usize) -> Result<PuzzleInput> {
+        self.input.get_example(day, &self.name).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-dir-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day03"), "abc\ndef\n").unwrap();
+        std::fs::write(dir.join("day4"), "1-2,3-4\n").unwrap();
+
+        let source = DirectorySource::new(&dir);
+        assert_eq!(source.get_input(3).await.unwrap().raw(), "abc\ndef\n");
+        assert_eq!(source.get_input(4).await.unwrap().raw(), "1-2,3-4\n");
+        assert!(matches!(
+            source.get_input(5).await,
+            Err(Error::MissingInputError(5))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::{self, Instant};
+
+/// Spaces requests out so that no two start within `interval` of each other, no matter how
+/// many tasks are making them.
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    next: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Throttle {
+            interval,
+            next: Mutex::new(None),
+        }
+    }
+
+    /// Wait until it's this caller's turn to make a request.
+    pub async fn wait(&self) {
+        // holding the lock while sleeping queues up the other callers behind this one
+        let mut next = self.next.lock().await;
+        if let Some(instant) = *next {
+            time::sleep_until(instant).await;
+        }
+
+        *next = Some(Instant::now() + self.interval);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn spacing() {
+        let throttle = Throttle::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        futures::join!(throttle.wait(), throttle.wait(), throttle.wait());
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use crate::challenge::error::Error as ChallengeError;
+use crate::challenge::{Answer, ChallengePart};
+use crate::error::Error;
+
+/// Answers known to be correct, used to check that refactoring a solution doesn't change
+/// what it produces.
+///
+/// The ledger is a text file with one answer per line, as the day, the part number and the
+/// [encoded](Answer::encode) answer separated by tabs.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<(usize, ChallengePart), Answer>,
+}
+
+/// How an answer compares to the one in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Answer),
+    /// No answer has been recorded for the part yet
+    Unknown,
+}
+
+impl Ledger {
+    /// Load the ledger at `path`, which is empty if the file doesn't exist yet.
+    pub async fn load<P: Into<PathBuf>>(path: P) -> Result<Self, Error> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(Error::LedgerError(path, err)),
+        };
+
+        let mut ledger = Ledger {
+            path,
+            answers: BTreeMap::new(),
+        };
+
+        for (idx, line) in contents.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let (day, part, answer) =
+                parse_entry(line).map_err(|err| err.at_line(idx + 1, line))?;
+            ledger.answers.insert((day, part), answer);
+        }
+
+        Ok(ledger)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, day: usize, part: ChallengePart) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    /// The number of parts of a day with a recorded answer.
+    pub fn recorded(&self, day: usize) -> usize {
+        ChallengePart::all()
+            .into_iter()
+            .filter(|part| self.get(day, *part).is_some())
+            .count()
+    }
+
+    pub fn record(&mut self, day: usize, part: ChallengePart, answer: Answer) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn check(&self, day: usize, part: ChallengePart, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected.same_as(answer) => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub async fn save(&self) -> Result<(), Error> {
+        let contents = self
+            .answers
+            .iter()
+            .map(|((day, part), answer)| {
+                format!("{}\t{}\t{}\n", day, *part as i32, answer.encode())
+            })
+            .collect::<String>();
+
+        let written = async {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir).await?;
+            }
+
+            fs::write(&self.path, contents).await
+        };
+
+        written
+            .await
+            .map_err(|err| Error::LedgerError(self.path.clone(), err))
+    }
+}
+
+fn parse_entry(line: &str) -> Result<(usize, ChallengePart, Answer), ChallengeError> {
+    let mut fields = line.splitn(3, '\t');
+    let mut next_field = |name| {
+        fields
+            .next()
+            .ok_or_else(|| ChallengeError::missing_data(name))
+    };
+
+    let day = next_field("day")?.parse()?;
+    let part = match next_field("part")? {
+        "1" => ChallengePart::First,
+        "2" => ChallengePart::Second,
+        part => return Err(ChallengeError::invalid_answer(part)),
+    };
+    let answer = next_field("answer")?.parse()?;
+
+    Ok((day, part, answer))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-ledger-test-{}", std::process::id()))
+            .join("answers");
+
+        let mut ledger = Ledger::load(&path).await.unwrap();
+        assert_eq!(ledger.recorded(5), 0);
+
+        ledger.record(5, ChallengePart::Second, Answer::from("MCD"));
+        ledger.record(5, ChallengePart::First, Answer::from("CMZ"));
+        ledger.record(6, ChallengePart::First, Answer::UInt(7));
+        ledger.save().await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "5\t1\tstr:CMZ\n5\t2\tstr:MCD\n6\t1\tuint:7\n"
+        );
+
+        let ledger = Ledger::load(&path).await.unwrap();
+        assert_eq!(ledger.recorded(5), 2);
+        assert_eq!(
+            ledger.check(6, ChallengePart::First, &Answer::Int(7)),
+            Verdict::Correct
+        );
+        assert_eq!(
+            ledger.check(5, ChallengePart::First, &Answer::from("MCD")),
+            Verdict::Wrong(Answer::from("CMZ"))
+        );
+        assert_eq!(
+            ledger.check(6, ChallengePart::Second, &Answer::UInt(19)),
+            Verdict::Unknown
+        );
+
+        std::fs::write(&path, "5\t1\tstr:CMZ\n5\t3\tstr:MCD\n").unwrap();
+        let err = Ledger::load(&path).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ChallengeError(ChallengeError {
+                line: Some(ref line),
+                ..
+            }) if line.number == 2
+        ));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
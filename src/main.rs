@@ -1,45 +1,714 @@
 mod challenge;
+mod days;
 mod error;
 mod input;
+mod ledger;
+mod output;
+mod scaffold;
+mod watch;
 
-use clap::Parser;
+use std::fs::File;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
-use challenge::ChallengePart;
-use error::Error;
+use clap::builder::RangedU64ValueParser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgGroup, Args as ClapArgs, CommandFactory, FromArgMatches, Parser, Subcommand};
 
-use crate::challenge::{run_all_challenges, run_challenge};
+use challenge::{
+    bench_challenge, run_all_challenges, run_challenges, year2022, Answer, ChallengePart, Frames,
+    PartResult, RunOptions,
+};
+use days::Days;
+use error::{Chain, Error};
+use input::{
+    calendar, DirectorySource, ExampleSource, FetchStatus, Input, InputSource, MemorySource,
+    PuzzleInput,
+};
+use ledger::{Ledger, Verdict};
+use output::Format;
+use watch::WatchOptions;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    // running challenges is the default when no command is given
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// The Advent of Code year to run
+    #[arg(long, global = true, default_value_t = 2022)]
+    year: usize,
+
+    /// Session token used to download inputs [default: $AOC_SESSION, ./.session, or
+    /// $XDG_CONFIG_HOME/aoc/session]
+    #[arg(long, global = true)]
+    session: Option<String>,
+
+    /// Directory inputs are cached in [default: $AOC_CACHE_DIR or ./input]
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+
+    /// Never download inputs; fail if an input isn't cached
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Wait for puzzles that haven't unlocked yet, then download their inputs
+    #[arg(long, global = true, conflicts_with = "offline")]
+    wait: bool,
+
+    /// Show what challenges are doing on stderr; repeat for more detail
+    #[arg(long, short = 'v', global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// User-Agent header sent with every request to adventofcode.com
+    #[arg(long, global = true, default_value = input::DEFAULT_USER_AGENT)]
+    user_agent: String,
+}
+
+impl Args {
+    /// Parse the command line, rejecting the arguments of the default command when another
+    /// command is given, rather than silently ignoring them.
+    ///
+    /// clap's `args_conflicts_with_subcommands` would count global arguments as well, so
+    /// `aoc22 --offline list` would stop finding the command.
+    fn parse_checked() -> Self {
+        let mut cmd = Args::command();
+        let matches = cmd.get_matches_mut();
+
+        if matches.subcommand().is_some() {
+            let ignored = cmd.get_arguments().find(|arg| {
+                !arg.is_global_set()
+                    && matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            });
+
+            if let Some(arg) = ignored {
+                let message = format!("the argument '{}' can't be used with a command", arg);
+                cmd.error(ErrorKind::ArgumentConflict, message).exit();
+            }
+        }
+
+        Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit())
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run challenges and print their answers
+    Run(RunArgs),
+    /// List the implemented days, and whether their inputs are cached
+    List,
+    /// Show which puzzles of the year have unlocked, been implemented and been verified
+    Status,
+    /// Download the inputs of every released day that isn't cached yet, and with `--wait` the
+    /// next day to unlock too
+    Fetch,
+    /// Time challenges over several runs
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+
+        #[command(flatten)]
+        source: SourceArgs,
+
+        /// How many times to run each part
+        #[arg(long, short = 'n', default_value_t = 10)]
+        iterations: usize,
+    },
+    /// Rerun a day whenever its input or examples change, printing how the answers changed
+    Watch {
+        #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..=25))]
+        day: usize,
+
+        /// Parts to run, as `first`/`1` or `second`/`2` [default: both]
+        #[arg(value_enum)]
+        parts: Vec<ChallengePart>,
+
+        /// Watch this file instead of the day's cached input
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Also run the day against every file in this directory
+        #[arg(long, value_name = "DIR")]
+        examples: Option<PathBuf>,
+
+        /// How often to check the files for changes, in milliseconds
+        #[arg(long, default_value_t = 500, value_name = "MS")]
+        interval: u64,
+    },
+    /// Generate the module for a new day from a template, and register it
+    New {
+        #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..=25))]
+        day: usize,
+
+        /// Use the first example from the puzzle description as the input of the example test
+        #[arg(long)]
+        seed_example: bool,
+    },
+    /// Recover Day 5's starting stacks by running its moves backwards from the final stacks
+    #[command(group(ArgGroup::new("arrangement").required(true).args(["tops", "stacks"])))]
+    Unstack {
+        /// The part whose crane made the moves, as `first`/`1` or `second`/`2`
+        #[arg(value_enum, default_value = "first")]
+        part: ChallengePart,
+
+        /// The crates left on top of each stack, such as `CMZ`
+        #[arg(long)]
+        tops: Option<String>,
+
+        /// A file holding a diagram of the final stacks
+        #[arg(long, value_name = "PATH")]
+        stacks: Option<PathBuf>,
+
+        /// Take the moves from this file instead of the cached input
+        #[arg(long, conflicts_with = "example", value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Take the moves from an example in the puzzle description, by its position
+        #[arg(long, num_args = 0..=1, require_equals = true)]
+        #[arg(default_missing_value = "1", value_name = "NAME")]
+        example: Option<String>,
+    },
+    /// Check answers against the ones recorded as correct
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+
+        #[command(flatten)]
+        exec: ExecArgs,
+
+        /// Record the answers as correct instead of checking them
+        #[arg(long)]
+        record: bool,
+    },
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    #[command(flatten)]
+    source: SourceArgs,
+
+    #[command(flatten)]
+    exec: ExecArgs,
+
+    /// Show how each part works, for the days that can, as frames drawn on stderr; parts are
+    /// run one at a time
+    #[arg(long)]
+    visualize: bool,
+
+    /// How long each frame is shown for, in milliseconds
+    #[arg(long, requires = "visualize", default_value_t = 250, value_name = "MS")]
+    frame_delay: u64,
+
+    /// Write every frame to this file instead of drawing them
+    #[arg(long, requires = "visualize", value_name = "PATH")]
+    frames: Option<PathBuf>,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value = "table")]
+    format: Format,
+}
+
+/// How the parts are run, for the commands that run them.
+#[derive(ClapArgs, Debug, Clone)]
+struct ExecArgs {
+    /// How many parts to run at once [default: the number of CPUs]
+    #[arg(long, short = 'j', value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    jobs: Option<usize>,
+
+    /// Give up on a part after this many seconds, such as `10` or `0.5`
+    #[arg(long, value_parser = parse_timeout, value_name = "SECONDS")]
+    timeout: Option<Duration>,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+struct Selection {
+    /// Days to run, such as `5`, `1-6` or `1,3,5` [default: every implemented day]
     #[arg()]
-    day: Option<usize>,
+    days: Option<Days>,
 
+    /// Parts to run, as `first`/`1` or `second`/`2` [default: both]
     #[arg(value_enum)]
     parts: Vec<ChallengePart>,
 }
 
-#[tokio::main]
-async fn main() {
-    if let Err(err) = run().await {
-        eprintln!("error: {}", err);
+#[derive(ClapArgs, Debug, Clone)]
+struct SourceArgs {
+    /// Run against an example from the puzzle description instead of the input, by its
+    /// position in the description
+    #[arg(long, num_args = 0..=1, require_equals = true)]
+    #[arg(default_missing_value = "1", value_name = "NAME")]
+    example: Option<String>,
+
+    /// Take inputs from here instead of the cache: a directory is searched for files named
+    /// `dayNN` or `dayN`, while a file, or `-` for stdin, is the input of the one day selected
+    #[arg(long, conflicts_with = "example", value_name = "PATH")]
+    input: Option<PathBuf>,
+}
+
+/// Wherever the command line asked for inputs to come from.
+enum Source<'a> {
+    Cache(&'a Input),
+    Memory(MemorySource),
+    Directory(DirectorySource),
+    Example(ExampleSource<'a>),
+}
+
+impl InputSource for Source<'_> {
+    async fn get_input(&self, day: usize) -> input::Result<PuzzleInput> {
+        match self {
+            Source::Cache(src) => src.get_input(day).await,
+            Source::Memory(src) => src.get_input(day).await,
+            Source::Directory(src) => src.get_input(day).await,
+            Source::Example(src) => src.get_input(day).await,
+        }
     }
 }
 
+#[tokio::main]
+async fn main() -> ExitCode {
+    let code = match run().await {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("error: {:#}", Chain(&err));
+            err.exit_code()
+        }
+    };
+
+    // parts that timed out are still running on blocking threads, which the runtime would wait
+    // for when it shuts down
+    std::process::exit(code.into())
+}
+
 async fn run() -> Result<(), Error> {
-    let args = Args::parse();
-    let input_svc = input::Input::new().await?;
-    let results = match args {
-        Args { day: None, .. } => run_all_challenges(&input_svc).await?,
-        Args {
-            day: Some(d),
+    let args = Args::parse_checked();
+    let input_svc = input::Input::new(input::Config {
+        year: args.year,
+        session: args.session.clone(),
+        cache_dir: args.cache_dir.clone(),
+        offline: args.offline,
+        wait: args.wait,
+        user_agent: args.user_agent.clone(),
+        ..Default::default()
+    })?;
+
+    let command = args.command.unwrap_or(Command::Run(args.run));
+    if let Command::Run(_)
+    | Command::Bench { .. }
+    | Command::Watch { .. }
+    | Command::Verify { .. } = command
+    {
+        if challenge::registered_days(args.year).is_empty() {
+            return Err(challenge::error::Error::year_not_implemented(args.year).into());
+        }
+    }
+
+    match command {
+        Command::Run(run) => {
+            let source = source(&run.selection, &run.source, &input_svc).await?;
+            let mut options = run_options(&run.exec, args.verbose);
+            if run.visualize {
+                options.frames = Some(frames(&run)?);
+                options.jobs = 1;
+            }
+
+            run_days(input_svc.year(), &run, &options, &source).await
+        }
+        Command::List => list(&input_svc),
+        Command::Status => status(&input_svc).await,
+        Command::Fetch => fetch(&input_svc).await,
+        Command::Bench {
+            selection,
+            source: source_args,
+            iterations,
+        } => {
+            let source = source(&selection, &source_args, &input_svc).await?;
+            bench(&selection, iterations, &input_svc, &source).await
+        }
+        Command::Watch {
+            day,
             parts,
-        } => vec![run_challenge(d, parts, &input_svc).await?],
+            input,
+            examples,
+            interval,
+        } => {
+            let options = WatchOptions {
+                year: args.year,
+                day,
+                parts: if parts.is_empty() {
+                    ChallengePart::all()
+                } else {
+                    parts
+                },
+                input,
+                examples,
+                interval: Duration::from_millis(interval.max(1)),
+                verbosity: args.verbose,
+            };
+
+            watch::watch(&options, &input_svc).await
+        }
+        Command::New { day, seed_example } => {
+            let path = scaffold::new_day(args.year, day, seed_example, &input_svc).await?;
+            println!("created {}", path.display());
+            Ok(())
+        }
+        Command::Unstack {
+            part,
+            tops,
+            stacks,
+            input,
+            example,
+        } => {
+            // the stacks are only a puzzle in 2022's Day 5
+            if args.year != 2022 {
+                return Err(challenge::error::Error::year_not_implemented(args.year).into());
+            }
+
+            let arrangement = match (tops, stacks) {
+                (Some(tops), _) => tops,
+                (_, Some(path)) => tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|err| Error::StacksError(path.clone(), err))?,
+                _ => unreachable!("clap requires one of the arrangements"),
+            };
+
+            let source = match (input, example) {
+                (Some(path), _) => Source::Memory(MemorySource::from_file(5, &path).await?),
+                (_, Some(name)) => Source::Example(ExampleSource::new(&input_svc, &name)),
+                _ => Source::Cache(&input_svc),
+            };
+
+            let stacks = year2022::day5::unstack(part, &arrangement, &source).await?;
+            println!("{}", stacks);
+            Ok(())
+        }
+        Command::Verify {
+            selection,
+            exec,
+            record,
+        } => {
+            let options = run_options(&exec, args.verbose);
+            verify(&selection, &options, record, &input_svc).await
+        }
+    }
+}
+
+async fn source<'a>(
+    selection: &Selection,
+    args: &SourceArgs,
+    input_svc: &'a Input,
+) -> Result<Source<'a>, Error> {
+    let source = match (&args.input, &args.example) {
+        (Some(dir), _) if dir.is_dir() => Source::Directory(DirectorySource::new(dir)),
+        (Some(path), _) => match selection.days.as_ref().map(Days::as_slice) {
+            Some(&[day]) => Source::Memory(MemorySource::from_file(day, path).await?),
+            _ => return Err(Error::InputNeedsOneDay(path.clone())),
+        },
+        (_, Some(name)) => Source::Example(ExampleSource::new(input_svc, name)),
+        _ => Source::Cache(input_svc),
+    };
+
+    Ok(source)
+}
+
+fn run_options(exec: &ExecArgs, verbosity: u8) -> RunOptions {
+    let defaults = RunOptions::default();
+    RunOptions {
+        jobs: exec.jobs.unwrap_or(defaults.jobs),
+        timeout: exec.timeout,
+        verbosity,
+        ..defaults
+    }
+}
+
+fn frames(args: &RunArgs) -> Result<Frames, Error> {
+    let frames = match &args.frames {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| Error::FramesError(path.clone(), err))?;
+            Frames::File(Arc::new(Mutex::new(file)))
+        }
+        None => Frames::Animate(Duration::from_millis(args.frame_delay)),
+    };
+
+    Ok(frames)
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let seconds = s
+        .parse::<f64>()
+        .map_err(|_| format!("`{}` isn't a number of seconds", s))?;
+
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("`{}` isn't a positive number of seconds", s))
+}
+
+/// Run the selected days and parts, or every implemented day if none were selected.
+async fn run_selection<S: InputSource>(
+    year: usize,
+    selection: &Selection,
+    options: &RunOptions,
+    input_svc: &S,
+) -> (Vec<PartResult>, Vec<challenge::error::Error>) {
+    let parts = if selection.parts.is_empty() {
+        ChallengePart::all()
+    } else {
+        selection.parts.clone()
     };
 
-    println!("results:");
-    for result_set in results {
-        println!("{:?}", result_set);
+    let days = match &selection.days {
+        None => run_all_challenges(year, options, input_svc).await,
+        Some(days) => run_challenges(year, days.as_slice(), &parts, options, input_svc).await,
+    };
+
+    let mut results = vec![];
+    let mut failed_days = vec![];
+    for day in days {
+        match day {
+            Ok(parts) => results.extend(parts),
+            Err(err) => failed_days.push(err),
+        }
+    }
+
+    (results, failed_days)
+}
+
+async fn run_days<S: InputSource>(
+    year: usize,
+    args: &RunArgs,
+    options: &RunOptions,
+    input_svc: &S,
+) -> Result<(), Error> {
+    let start = Instant::now();
+    let (results, failed_days) = run_selection(year, &args.selection, options, input_svc).await;
+    let elapsed = start.elapsed();
+
+    let mut out = std::io::stdout().lock();
+    output::write_results(args.format, &results, &failed_days, &mut out)?;
+    if args.format == Format::Table {
+        output::write_timing(&results, elapsed, options.jobs, &mut out)?;
+    }
+    drop(out);
+
+    report_failures(
+        &failed_days,
+        part_errors(results.iter().map(|result| &result.answer)),
+    )
+}
+
+/// The errors of the parts that failed, given every part's answer.
+fn part_errors<'a>(
+    answers: impl IntoIterator<Item = &'a challenge::Result<Answer>>,
+) -> Vec<&'a challenge::error::Error> {
+    answers
+        .into_iter()
+        .filter_map(|answer| answer.as_ref().err())
+        .collect()
+}
+
+/// Print a summary of everything that failed, if anything did, and turn the failures into an
+/// error that exits with the right code.
+fn report_failures(
+    failed_days: &[challenge::error::Error],
+    failed_parts: Vec<&challenge::error::Error>,
+) -> Result<(), Error> {
+    let failures = failed_days.len() + failed_parts.len();
+    if failures == 0 {
+        return Ok(());
+    }
+
+    output::write_failures(failed_days, &failed_parts, &mut std::io::stderr().lock())?;
+
+    // nothing was wrong with the challenges themselves if none of them could even be loaded
+    let inputs_failed = failed_days
+        .iter()
+        .all(|err| matches!(err.kind, challenge::error::ErrorKind::InputError(_)));
+    if failed_parts.is_empty() && inputs_failed {
+        return Err(Error::InputsFailed(failures));
+    }
+
+    Err(Error::ChallengesFailed(failures))
+}
+
+fn list(input_svc: &Input) -> Result<(), Error> {
+    let days = challenge::registered_days(input_svc.year());
+    if days.is_empty() {
+        println!("no days have been implemented for {}", input_svc.year());
+        return Ok(());
+    }
+
+    let title_width = days.iter().map(|(_, title)| title.len()).max().unwrap_or(0);
+    println!("day  {:<title_width$}  input", "title");
+    for (day, title) in days {
+        let cached = if input_svc.is_cached(*day) {
+            "cached"
+        } else {
+            "not cached"
+        };
+
+        println!("{:<3}  {:<title_width$}  {}", day, title, cached);
+    }
+
+    Ok(())
+}
+
+async fn status(input_svc: &Input) -> Result<(), Error> {
+    let year = input_svc.year();
+    let implemented = challenge::registered_days(year);
+    let ledger = Ledger::load(input_svc.answers_path()).await?;
+    let now = SystemTime::now();
+
+    println!("day  unlocked     implemented  input       answers");
+    for day in 1..=calendar::days_in_year(year) {
+        let unlocked = match calendar::time_until_unlock(year, day, now) {
+            None => "yes".to_string(),
+            Some(remaining) => format!("in {}", calendar::format_hms(&remaining)),
+        };
+        let implemented = if implemented.iter().any(|(d, _)| *d == day) {
+            "yes"
+        } else {
+            "no"
+        };
+        let cached = if input_svc.is_cached(day) {
+            "cached"
+        } else {
+            "not cached"
+        };
+
+        println!(
+            "{:<3}  {:<11}  {:<11}  {:<10}  {}/2",
+            day,
+            unlocked,
+            implemented,
+            cached,
+            ledger.recorded(day)
+        );
+    }
+
+    Ok(())
+}
+
+async fn fetch(input_svc: &Input) -> Result<(), Error> {
+    let mut failures = 0;
+    for day in input_svc.fetchable_days() {
+        let until_unlock = calendar::time_until_unlock(input_svc.year(), day, SystemTime::now());
+        if let Some(remaining) = until_unlock {
+            println!(
+                "day {}: waiting {} for it to unlock",
+                day,
+                calendar::format_hms(&remaining)
+            );
+        }
+
+        match input_svc.fetch(day).await {
+            Ok(FetchStatus::Cached) => println!("day {}: cached", day),
+            Ok(FetchStatus::Downloaded) => println!("day {}: downloaded", day),
+            Err(err) => {
+                eprintln!("day {}: {}", day, Chain(&err));
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(Error::FetchFailed(failures));
+    }
+
+    Ok(())
+}
+
+async fn bench<S: InputSource>(
+    selection: &Selection,
+    iterations: usize,
+    input_svc: &Input,
+    source: &S,
+) -> Result<(), Error> {
+    let year = input_svc.year();
+    let parts = if selection.parts.is_empty() {
+        ChallengePart::all()
+    } else {
+        selection.parts.clone()
+    };
+    let days = match &selection.days {
+        Some(days) => days.as_slice().to_vec(),
+        None => challenge::registered_days(year)
+            .iter()
+            .map(|(day, _)| *day)
+            .collect(),
+    };
+
+    let mut results = vec![];
+    let mut failed_days = vec![];
+    for day in days {
+        match bench_challenge(year, day, &parts, iterations, source).await {
+            Ok(parts) => results.extend(parts),
+            Err(err) => failed_days.push(err),
+        }
+    }
+
+    output::write_bench(&results, &mut std::io::stdout().lock())?;
+
+    report_failures(
+        &failed_days,
+        part_errors(results.iter().map(|result| &result.answer)),
+    )
+}
+
+async fn verify(
+    selection: &Selection,
+    options: &RunOptions,
+    record: bool,
+    input_svc: &Input,
+) -> Result<(), Error> {
+    let mut ledger = Ledger::load(input_svc.answers_path()).await?;
+    let (results, failed_days) =
+        run_selection(input_svc.year(), selection, options, input_svc).await;
+
+    let mut wrong = 0;
+    let results = results
+        .into_iter()
+        .map(|result| {
+            let verdict = result.answer.as_ref().ok().map(|answer| {
+                if record {
+                    ledger.record(result.day, result.part, answer.clone());
+                    return Verdict::Correct;
+                }
+
+                ledger.check(result.day, result.part, answer)
+            });
+
+            if let Some(Verdict::Wrong(_)) = verdict {
+                wrong += 1;
+            }
+
+            (result, verdict)
+        })
+        .collect::<Vec<_>>();
+
+    output::write_verification(&results, &mut std::io::stdout().lock())?;
+
+    if record {
+        ledger.save().await?;
+        println!("answers recorded in {}", ledger.path().display());
+    }
+
+    let answers = results.iter().map(|(result, _)| &result.answer);
+    report_failures(&failed_days, part_errors(answers))?;
+
+    if wrong > 0 {
+        return Err(Error::WrongAnswers(wrong));
     }
 
     Ok(())
@@ -0,0 +1,513 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::challenge::error::Error;
+use crate::challenge::{Answer, BenchResult, PartResult};
+use crate::error::Chain;
+use crate::ledger::Verdict;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Table,
+    /// A JSON array with one object per part
+    Json,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+/// Print the results of a run. Days that couldn't be set up at all are only summarised by
+/// [`write_failures`] in a table, but get a record of their own (without a part) in the
+/// structured formats, so that scripts reading them can tell those days failed.
+pub fn write_results(
+    format: Format,
+    results: &[PartResult],
+    failed_days: &[Error],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    match format {
+        Format::Table => write_table(results, out),
+        Format::Json => write_json(&records(results, failed_days), out),
+        Format::Tsv => write_tsv(&records(results, failed_days), out),
+    }
+}
+
+/// Compare how long a run took with the time spent solving each part, which is longer when
+/// parts ran at the same time.
+pub fn write_timing(
+    results: &[PartResult],
+    elapsed: Duration,
+    jobs: usize,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let solving = results
+        .iter()
+        .map(|result| result.duration)
+        .sum::<Duration>();
+
+    writeln!(
+        out,
+        "{} part(s) took {:.2?} to solve, in {:.2?} of wall-clock time with {} job(s)",
+        results.len(),
+        solving,
+        elapsed,
+        jobs
+    )
+}
+
+/// Summarise every failure from a run: days that couldn't be set up at all, followed by
+/// individual parts that returned an error. Errors caused by a particular line of input are
+/// followed by a snippet pointing it out.
+pub fn write_failures(
+    failed_days: &[Error],
+    failed_parts: &[&Error],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(out, "failures:")?;
+    for err in failed_days.iter().chain(failed_parts.iter().copied()) {
+        write_error(err, out)?;
+    }
+
+    Ok(())
+}
+
+/// Print the timings of benchmarked parts, followed by their answers.
+pub fn write_bench(results: &[BenchResult], out: &mut impl Write) -> io::Result<()> {
+    let rows = results
+        .iter()
+        .map(|result| {
+            vec![
+                result.day.to_string(),
+                result.part.to_string(),
+                result.durations.len().to_string(),
+                format!("{:.2?}", result.min()),
+                format!("{:.2?}", result.mean()),
+                format!("{:.2?}", result.max()),
+                answer_text(&result.answer),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    write_columns(
+        &["day", "part", "runs", "min", "mean", "max", "answer"],
+        &rows,
+        out,
+    )
+}
+
+/// Print each part's answer next to how it compares to the answer recorded for it.
+pub fn write_verification(
+    results: &[(PartResult, Option<Verdict>)],
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let rows = results
+        .iter()
+        .map(|(result, verdict)| {
+            let status = match verdict {
+                Some(Verdict::Correct) => "ok".to_string(),
+                Some(Verdict::Wrong(expected)) => format!("wrong, expected {}", expected),
+                Some(Verdict::Unknown) => "unknown".to_string(),
+                None => "error".to_string(),
+            };
+
+            vec![
+                result.day.to_string(),
+                result.part.to_string(),
+                status,
+                answer_text(&result.answer),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    write_columns(&["day", "part", "result", "answer"], &rows, out)
+}
+
+fn answer_text(answer: &Result<Answer, Error>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("error: {}", Chain(err)),
+    }
+}
+
+/// Print rows of left-aligned columns under a header. Only the last column may span several
+/// lines, with its continuation lines lined up underneath it.
+fn write_columns(header: &[&str], rows: &[Vec<String>], out: &mut impl Write) -> io::Result<()> {
+    let last = header.len() - 1;
+    let widths = (0..last)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].len())
+                .fold(header[col].len(), usize::max)
+        })
+        .collect::<Vec<_>>();
+
+    for (col, width) in widths.iter().enumerate() {
+        write!(out, "{:<width$}  ", header[col])?;
+    }
+    writeln!(out, "{}", header[last])?;
+
+    for row in rows {
+        let mut lines = row[last].lines();
+
+        for (col, width) in widths.iter().enumerate() {
+            write!(out, "{:<width$}  ", row[col])?;
+        }
+        writeln!(out, "{}", lines.next().unwrap_or_default())?;
+
+        for line in lines {
+            writeln!(
+                out,
+                "{:width$}{}",
+                "",
+                line,
+                width = widths.iter().sum::<usize>() + 2 * last
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_error(err: &Error, out: &mut impl Write) -> io::Result<()> {
+    match (err.day(), err.part()) {
+        (Some(day), Some(part)) => writeln!(out, "  day {} part {}: {}", day, part, Chain(err))?,
+        (Some(day), None) => writeln!(out, "  day {}: {}", day, Chain(err))?,
+        _ => writeln!(out, "  {}", Chain(err))?,
+    }
+
+    if let Some(line) = err.line() {
+        for snippet_line in line.snippet().lines() {
+            writeln!(out, "    {}", snippet_line)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_table(results: &[PartResult], out: &mut impl Write) -> io::Result<()> {
+    let rows = results
+        .iter()
+        .map(|result| {
+            let answer = match &result.answer {
+                Ok(answer) => answer.to_string(),
+                Err(err) => format!("error: {}", Chain(err)),
+            };
+
+            (
+                result.day.to_string(),
+                result.part.to_string(),
+                format!("{:.2?}", result.duration),
+                answer,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let day_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0).max(3);
+    let part_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0).max(4);
+    let time_width = rows.iter().map(|row| row.2.len()).max().unwrap_or(0).max(4);
+
+    writeln!(
+        out,
+        "{:<day_width$}  {:<part_width$}  {:>time_width$}  answer",
+        "day", "part", "time"
+    )?;
+
+    for (day, part, time, answer) in rows {
+        let mut lines = answer.lines();
+        writeln!(
+            out,
+            "{:<day_width$}  {:<part_width$}  {:>time_width$}  {}",
+            day,
+            part,
+            time,
+            lines.next().unwrap_or_default()
+        )?;
+
+        // continuation lines of multiline answers line up under the answer column
+        for line in lines {
+            writeln!(
+                out,
+                "{:<day_width$}  {:<part_width$}  {:>time_width$}  {}",
+                "", "", "", line
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// One row of structured output: either a part that ran, or a day that failed before any of
+/// its parts could.
+enum Record<'a> {
+    Part(&'a PartResult),
+    Day(usize, &'a Error),
+}
+
+/// Interleave the failed days with the part results, keeping everything in order of day.
+fn records<'a>(results: &'a [PartResult], failed_days: &'a [Error]) -> Vec<Record<'a>> {
+    let mut records = results
+        .iter()
+        .map(Record::Part)
+        .chain(
+            failed_days
+                .iter()
+                .map(|err| Record::Day(err.day().unwrap_or_default(), err)),
+        )
+        .collect::<Vec<_>>();
+
+    records.sort_by_key(|record| match record {
+        Record::Part(result) => result.day,
+        Record::Day(day, _) => *day,
+    });
+
+    records
+}
+
+fn write_json(records: &[Record], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "[")?;
+
+    for (idx, record) in records.iter().enumerate() {
+        let (day, part, answer, duration, error) = match record {
+            Record::Part(result) => (
+                result.day,
+                (result.part as i32).to_string(),
+                result
+                    .answer
+                    .as_ref()
+                    .map_or("null".to_string(), json_answer),
+                result.duration.as_secs_f64().to_string(),
+                result.answer.as_ref().err(),
+            ),
+            Record::Day(day, err) => (
+                *day,
+                "null".to_string(),
+                "null".to_string(),
+                "null".to_string(),
+                Some(*err),
+            ),
+        };
+
+        let (error, line) = match error {
+            Some(err) => (
+                json_string(&Chain(err).to_string()),
+                err.line()
+                    .map_or("null".to_string(), |line| line.number.to_string()),
+            ),
+            None => ("null".to_string(), "null".to_string()),
+        };
+
+        let separator = if idx + 1 < records.len() { "," } else { "" };
+        writeln!(
+            out,
+            r#"  {{"day": {}, "part": {}, "answer": {}, "duration": {}, "error": {}, "line": {}}}{}"#,
+            day, part, answer, duration, error, line, separator
+        )?;
+    }
+
+    writeln!(out, "]")
+}
+
+fn write_tsv(records: &[Record], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "day\tpart\tanswer\tduration\terror\tline")?;
+
+    for record in records {
+        let (day, part, answer, duration, error) = match record {
+            Record::Part(result) => (
+                result.day,
+                (result.part as i32).to_string(),
+                result
+                    .answer
+                    .as_ref()
+                    .map_or(String::new(), |answer| tsv_field(&answer.to_string())),
+                result.duration.as_secs_f64().to_string(),
+                result.answer.as_ref().err(),
+            ),
+            Record::Day(day, err) => (
+                *day,
+                String::new(),
+                String::new(),
+                String::new(),
+                Some(*err),
+            ),
+        };
+
+        let (error, line) = match error {
+            Some(err) => (
+                tsv_field(&Chain(err).to_string()),
+                err.line()
+                    .map_or(String::new(), |line| line.number.to_string()),
+            ),
+            None => (String::new(), String::new()),
+        };
+
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            day, part, answer, duration, error, line
+        )?;
+    }
+
+    Ok(())
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::UInt(n) => n.to_string(),
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+/// Tabs and line breaks can't appear inside a TSV field, so they're escaped the same way
+/// as in JSON strings.
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::challenge::ChallengePart;
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 5,
+                part: ChallengePart::First,
+                answer: Ok(Answer::from("CMZ")),
+                duration: Duration::from_millis(250),
+            },
+            PartResult {
+                day: 5,
+                part: ChallengePart::Second,
+                answer: Err(Error::missing_data("crate \"stack\"")
+                    .at_line(3, "[Z] [M] [P]")
+                    .in_part(ChallengePart::Second)
+                    .in_day(5)),
+                duration: Duration::from_millis(500),
+            },
+            PartResult {
+                day: 6,
+                part: ChallengePart::First,
+                answer: Ok(Answer::UInt(7)),
+                duration: Duration::from_secs(1),
+            },
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut out = vec![];
+        let failed_days = [Error::too_many_lines().in_day(3)];
+        write_results(format, &results(), &failed_days, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json() {
+        let expected = concat!(
+            "[\n",
+            r#"  {"day": 3, "part": null, "answer": null, "duration": null, "error": "too many lines of input", "line": null},"#,
+            "\n",
+            r#"  {"day": 5, "part": 1, "answer": "CMZ", "duration": 0.25, "error": null, "line": null},"#,
+            "\n",
+            r#"  {"day": 5, "part": 2, "answer": null, "duration": 0.5, "error": "missing data in challenge: crate \"stack\"", "line": 3},"#,
+            "\n",
+            r#"  {"day": 6, "part": 1, "answer": 7, "duration": 1, "error": null, "line": null}"#,
+            "\n]\n",
+        );
+
+        assert_eq!(render(Format::Json), expected);
+    }
+
+    #[test]
+    fn tsv() {
+        let expected = concat!(
+            "day\tpart\tanswer\tduration\terror\tline\n",
+            "3\t\t\t\ttoo many lines of input\t\n",
+            "5\t1\tCMZ\t0.25\t\t\n",
+            "5\t2\t\t0.5\tmissing data in challenge: crate \"stack\"\t3\n",
+            "6\t1\t7\t1\t\t\n",
+        );
+
+        assert_eq!(render(Format::Tsv), expected);
+    }
+
+    #[test]
+    fn verification() {
+        let verdicts = [
+            Some(Verdict::Correct),
+            None,
+            Some(Verdict::Wrong(Answer::UInt(11))),
+        ];
+        let results = results().into_iter().zip(verdicts).collect::<Vec<_>>();
+
+        let mut out = vec![];
+        write_verification(&results, &mut out).unwrap();
+
+        let expected = concat!(
+            "day  part    result              answer\n",
+            "5    first   ok                  CMZ\n",
+            "5    second  error               error: missing data in challenge: crate \"stack\"\n",
+            "6    first   wrong, expected 11  7\n",
+        );
+
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn failures() {
+        let mut out = vec![];
+        let results = results();
+        let failed_parts = results
+            .iter()
+            .filter_map(|result| result.answer.as_ref().err())
+            .collect::<Vec<_>>();
+
+        write_failures(
+            &[Error::too_many_lines().in_day(3)],
+            &failed_parts,
+            &mut out,
+        )
+        .unwrap();
+
+        let expected = concat!(
+            "failures:\n",
+            "  day 3: too many lines of input\n",
+            "  day 5 part second: missing data in challenge: crate \"stack\"\n",
+            "     --> input line 3\n",
+            "      |\n",
+            "    3 | [Z] [M] [P]\n",
+            "      | ^^^^^^^^^^^\n",
+        );
+
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+}
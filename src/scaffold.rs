@@ -0,0 +1,322 @@
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use crate::error::{Chain, Error};
+use crate::input::{Input, PuzzleInput};
+
+/// Where the challenges live, found at build time so that `new` works from any directory.
+const CHALLENGE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/challenge");
+
+const DAY_TEMPLATE: &str = r#"use super::error::Error;
+use super::{Answer, Challenge, ChallengePart, Context, Result};
+
+use crate::input::PuzzleInput;
+
+pub struct Day{{DAY}} {
+    r#in: PuzzleInput,
+}
+
+impl Day{{DAY}} {
+    pub fn new(r#in: PuzzleInput) -> Self {
+        Self { r#in }
+    }
+
+    fn run_first(&self) -> Result<i64> {
+        let _lines = self.r#in.lines();
+        Err(Error::no_solution())
+    }
+
+    fn run_second(&self) -> Result<i64> {
+        let _lines = self.r#in.lines();
+        Err(Error::no_solution())
+    }
+}
+
+impl Challenge for Day{{DAY}} {
+    fn run(&self, part: ChallengePart, _ctx: &Context) -> Result<Answer> {
+        match part {
+            ChallengePart::First => self.run_first().map(Answer::from),
+            ChallengePart::Second => self.run_second().map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn example() {
+        // the first example from the puzzle description; paste it in if it wasn't fetched
+        let input = PuzzleInput::from(include_str!("examples/day{{DAY}}"));
+
+        let challenge = Day{{DAY}}::new(input);
+
+        let result = challenge.run_first();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 0);
+
+        let result = challenge.run_second();
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 0);
+    }
+}
+"#;
+
+const YEAR_TEMPLATE: &str = r#"use super::{error, Answer, Challenge, ChallengePart, Context, DynChallenge, Result};
+
+use error::Error;
+
+use crate::input::InputSource;
+
+/// Every implemented day, with the title of its puzzle.
+pub(super) const DAYS: &[(usize, &str)] = &[
+];
+
+pub(super) async fn get_challenge<S: InputSource>(
+    day: usize,
+    input_svc: &S,
+) -> Result<DynChallenge> {
+    match day {
+        day => Err(Error::not_implemented(day)),
+    }
+}
+"#;
+
+/// Generate the module for a new day from a template, and register it with its year.
+///
+/// The puzzle's title is taken from its description. The generated example test reads its input
+/// from `examples/dayN` next to the day, which holds the description's first example if
+/// `seed_example` is set, and is left empty otherwise.
+pub async fn new_day(
+    year: usize,
+    day: usize,
+    seed_example: bool,
+    input_svc: &Input,
+) -> Result<PathBuf, Error> {
+    let year_dir = Path::new(CHALLENGE_DIR).join(format!("year{}", year));
+    let path = year_dir.join(format!("day{}.rs", day));
+    if path.exists() {
+        return Err(Error::DayExists(path));
+    }
+
+    let title = match input_svc.get_title(day).await {
+        Ok(title) => title,
+        Err(err) => {
+            eprintln!(
+                "warning: couldn't get the title of day {}: {}",
+                day,
+                Chain(&err)
+            );
+            format!("Day {}", day)
+        }
+    };
+
+    let example = if seed_example {
+        input_svc.get_example(day, "1").await?
+    } else {
+        PuzzleInput::default()
+    };
+
+    let year_mod = year_dir.join("mod.rs");
+    let year_src = if year_mod.exists() {
+        read(&year_mod).await?
+    } else {
+        let challenge_mod = Path::new(CHALLENGE_DIR).join("mod.rs");
+        let challenge_src = read(&challenge_mod).await?;
+        let challenge_src = register_year(&challenge_src, year)
+            .ok_or_else(|| Error::RegisterError(challenge_mod.clone()))?;
+        write(&challenge_mod, &challenge_src).await?;
+
+        YEAR_TEMPLATE.to_string()
+    };
+
+    let year_src = register_day(&year_src, day, &title)
+        .ok_or_else(|| Error::RegisterError(year_mod.clone()))?;
+
+    // an example that's already there was put there by hand, so keep it
+    let example_path = year_dir.join("examples").join(format!("day{}", day));
+    if !example_path.exists() {
+        write(&example_path, example.raw()).await?;
+    }
+
+    write(&path, &day_source(day)).await?;
+    write(&year_mod, &year_src).await?;
+
+    Ok(path)
+}
+
+fn day_source(day: usize) -> String {
+    DAY_TEMPLATE.replace("{{DAY}}", &day.to_string())
+}
+
+/// Add a day's `mod` line, title and match arm to its year's module, keeping them in order.
+fn register_day(src: &str, day: usize, title: &str) -> Option<String> {
+    let mut lines = src.lines().map(String::from).collect::<Vec<_>>();
+
+    let day_of = |line: &str, prefix: &str, suffix: &str| -> Option<usize> {
+        line.trim()
+            .strip_prefix(prefix)?
+            .split(suffix)
+            .next()?
+            .parse()
+            .ok()
+    };
+
+    // the match arm goes before the first later day, or the catch-all arm
+    let arm = lines.iter().position(|line| {
+        day_of(line, "day @ ", " =>").is_some_and(|other| other > day)
+            || line.trim().starts_with("day => ")
+    })?;
+    lines.insert(
+        arm,
+        format!(
+            "        day @ {0} => challenge!(day, input_svc => day{0}::Day{0}),",
+            day
+        ),
+    );
+
+    let title_end = lines.iter().position(|line| line.starts_with("];"))?;
+    let entry = lines[..title_end]
+        .iter()
+        .position(|line| day_of(line, "(", ",").is_some_and(|other| other > day))
+        .unwrap_or(title_end);
+    lines.insert(entry, format!("    ({}, {:?}),", day, title));
+
+    // mod lines go at the top, below any comment, before the first later day or the first line
+    // that isn't one
+    let module = lines.iter().position(|line| {
+        !line.starts_with("//")
+            && day_of(without_pub(line), "mod day", ";").is_none_or(|other| other > day)
+    })?;
+    lines.insert(module, format!("mod day{};", day));
+    if !without_pub(&lines[module + 1]).starts_with("mod day") && !lines[module + 1].is_empty() {
+        lines.insert(module + 1, String::new());
+    }
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Add a new year's module to the challenge registry.
+fn register_year(src: &str, year: usize) -> Option<String> {
+    let mut lines = src.lines().map(String::from).collect::<Vec<_>>();
+
+    let module = lines
+        .iter()
+        .rposition(|line| without_pub(line).starts_with("mod year"))?;
+    lines.insert(module + 1, format!("mod year{};", year));
+
+    let dispatch = lines
+        .iter()
+        .position(|line| line.trim().starts_with("year => Err("))?;
+    lines.insert(
+        dispatch,
+        format!(
+            "        {0} => year{0}::get_challenge(day, input_svc).await,",
+            year
+        ),
+    );
+
+    let registered = lines
+        .iter()
+        .position(|line| line.trim().starts_with("_ => &[]"))?;
+    lines.insert(registered, format!("        {0} => year{0}::DAYS,", year));
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// A `mod` line without its `pub`, since a module may be public to expose more than its days.
+fn without_pub(line: &str) -> &str {
+    line.strip_prefix("pub ").unwrap_or(line)
+}
+
+async fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .await
+        .map_err(|err| Error::ScaffoldError(path.to_path_buf(), err))
+}
+
+async fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    let written = async {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+
+        fs::write(path, contents).await
+    };
+
+    written
+        .await
+        .map_err(|err| Error::ScaffoldError(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn register() {
+        let src = YEAR_TEMPLATE.to_string();
+        let src = register_day(&src, 7, "No Space Left On Device").unwrap();
+        let src = register_day(&src, 2, "Rock Paper Scissors").unwrap();
+
+        assert!(src.starts_with("mod day2;\nmod day7;\n\nuse super::"));
+        assert!(src.contains(concat!(
+            "    (2, \"Rock Paper Scissors\"),\n",
+            "    (7, \"No Space Left On Device\"),\n",
+            "];"
+        )));
+        assert!(src.contains(concat!(
+            "        day @ 2 => challenge!(day, input_svc => day2::Day2),\n",
+            "        day @ 7 => challenge!(day, input_svc => day7::Day7),\n",
+            "        day => Err(Error::not_implemented(day)),\n",
+        )));
+
+        // a year whose modules are commented and partly public, as year2022's are
+        let year = format!(
+            "// the examples are kept under examples/\nmod day1;\npub mod day5;\n\n{}",
+            YEAR_TEMPLATE
+        );
+        let year = register_day(&year, 3, "Rucksack Reorganization").unwrap();
+        assert!(year.starts_with(concat!(
+            "// the examples are kept under examples/\n",
+            "mod day1;\n",
+            "mod day3;\n",
+            "pub mod day5;\n",
+            "\n",
+        )));
+
+        let registry = concat!(
+            "pub mod year2022;\n",
+            "\n",
+            "fn get_challenge() {\n",
+            "    match year {\n",
+            "        2022 => year2022::get_challenge(day, input_svc).await,\n",
+            "        year => Err(Error::year_not_implemented(year)),\n",
+            "    }\n",
+            "}\n",
+            "\n",
+            "fn registered_days() {\n",
+            "    match year {\n",
+            "        2022 => year2022::DAYS,\n",
+            "        _ => &[],\n",
+            "    }\n",
+            "}\n",
+        );
+        let registry = register_year(registry, 2023).unwrap();
+        assert!(registry.contains("pub mod year2022;\nmod year2023;\n"));
+        assert!(registry.contains("2023 => year2023::get_challenge(day, input_svc).await,"));
+        assert!(registry.contains("2023 => year2023::DAYS,"));
+    }
+
+    #[test]
+    fn template() {
+        let src = day_source(5);
+
+        assert!(src.contains("pub struct Day5 {"));
+        assert!(src.contains("let input = PuzzleInput::from(include_str!(\"examples/day5\"));"));
+        assert!(src.contains("let challenge = Day5::new(input);"));
+    }
+}
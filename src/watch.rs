@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tokio::{fs, signal, time};
+
+use crate::challenge::{run_challenge, ChallengePart, RunOptions};
+use crate::error::{Chain, Error};
+use crate::input::{Input, MemorySource};
+
+/// What to rerun, and which files to watch for changes.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub year: usize,
+    pub day: usize,
+    pub parts: Vec<ChallengePart>,
+    /// Watch this file instead of the day's cached input
+    pub input: Option<PathBuf>,
+    /// Every file in this directory is run as an extra input
+    pub examples: Option<PathBuf>,
+    /// How often the files are checked for changes
+    pub interval: Duration,
+    /// How much the parts report about themselves on stderr
+    pub verbosity: u8,
+}
+
+/// When each watched file was last modified and how big it was, or `None` if it's missing.
+type Snapshot = BTreeMap<PathBuf, Option<(SystemTime, u64)>>;
+
+/// The answers from one run, keyed by the name of the input they're for and the part.
+/// Errors are kept as their message, so a part that starts or stops failing shows up as a change.
+type Answers = BTreeMap<(String, ChallengePart), String>;
+
+/// Rerun a day whenever its input or any of its examples change, until interrupted.
+///
+/// The files are polled rather than watched through the operating system, which is plenty for
+/// a handful of small files and doesn't need anything running outside the process.
+pub async fn watch(options: &WatchOptions, input_svc: &Input) -> Result<(), Error> {
+    let input_path = match &options.input {
+        Some(path) => path.clone(),
+        None => {
+            input_svc.fetch(options.day).await?;
+            input_svc.cached_path(options.day)
+        }
+    };
+
+    let mut ticks = time::interval(options.interval);
+    let mut last_seen = Snapshot::new();
+    let mut previous = Answers::new();
+    let mut run = 0;
+
+    // made once, so that an interrupt is noticed while the day is running as well as between
+    // ticks, and isn't lost in between
+    let interrupted = signal::ctrl_c();
+    tokio::pin!(interrupted);
+
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {}
+            _ = &mut interrupted => return Ok(()),
+        }
+
+        let seen = snapshot(&input_path, options.examples.as_deref()).await;
+        if run > 0 && seen == last_seen {
+            continue;
+        }
+
+        run += 1;
+        let changed = changed_files(&last_seen, &seen);
+        last_seen = seen;
+
+        let answers = tokio::select! {
+            answers = run_inputs(options, &input_path) => answers,
+            _ = &mut interrupted => return Ok(()),
+        };
+
+        let mut out = io::stdout().lock();
+        if run == 1 {
+            writeln!(out, "day {}, run {}", options.day, run)?;
+        } else {
+            writeln!(
+                out,
+                "day {}, run {}: {} changed",
+                options.day,
+                run,
+                changed.join(", ")
+            )?;
+        }
+        write_changes(&previous, &answers, &mut out)?;
+        out.flush()?;
+
+        previous = answers;
+    }
+}
+
+async fn snapshot(input: &Path, examples: Option<&Path>) -> Snapshot {
+    let mut paths = vec![input.to_path_buf()];
+    if let Some(dir) = examples {
+        paths.extend(example_files(dir).await);
+    }
+
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        let modified = fs::metadata(&path)
+            .await
+            .ok()
+            .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+
+        snapshot.insert(path, modified);
+    }
+
+    snapshot
+}
+
+/// Every regular file in the examples directory, in order of name.
+async fn example_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+
+    if let Ok(mut entries) = fs::read_dir(dir).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.file_type().await.is_ok_and(|kind| kind.is_file()) {
+                files.push(entry.path());
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<String> {
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|path| before.get(*path) != after.get(*path))
+        .map(|path| file_name(path))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+async fn run_inputs(options: &WatchOptions, input_path: &Path) -> Answers {
+    let mut inputs = vec![("input".to_string(), input_path.to_path_buf())];
+    if let Some(dir) = &options.examples {
+        for path in example_files(dir).await {
+            inputs.push((file_name(&path), path));
+        }
+    }
+
+    let mut answers = Answers::new();
+    for (name, path) in inputs {
+        let results = match MemorySource::from_file(options.day, &path).await {
+            Ok(source) => {
+                let parts = options.parts.clone();
+                let run_options = RunOptions {
+                    verbosity: options.verbosity,
+                    ..Default::default()
+                };
+
+                run_challenge(options.year, options.day, parts, &run_options, &source).await
+            }
+            Err(err) => Err(err.into()),
+        };
+
+        for part in &options.parts {
+            let answer = match &results {
+                Ok(results) => match results.iter().find(|result| result.part == *part) {
+                    Some(result) => match &result.answer {
+                        Ok(answer) => answer.to_string(),
+                        Err(err) => format!("error: {}", Chain(err)),
+                    },
+                    None => continue,
+                },
+                Err(err) => format!("error: {}", Chain(err)),
+            };
+
+            answers.insert((name.clone(), *part), answer);
+        }
+    }
+
+    answers
+}
+
+/// Print every answer, noting how it differs from the previous run's.
+fn write_changes(previous: &Answers, answers: &Answers, out: &mut impl Write) -> io::Result<()> {
+    let name_width = answers
+        .keys()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    for ((name, part), answer) in answers {
+        let note = match previous.get(&(name.clone(), *part)) {
+            None if previous.is_empty() => String::new(),
+            None => "  (new)".to_string(),
+            Some(before) if before == answer => "  (unchanged)".to_string(),
+            Some(before) => format!("  (was {})", before.replace('\n', "\\n")),
+        };
+
+        writeln!(
+            out,
+            "  {:<name_width$}  {:<6}  {}{}",
+            name,
+            part.to_string(),
+            answer.replace('\n', "\\n"),
+            note
+        )?;
+    }
+
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn changes() {
+        let answers = |entries: &[(&str, ChallengePart, &str)]| {
+            entries
+                .iter()
+                .map(|(name, part, answer)| ((name.to_string(), *part), answer.to_string()))
+                .collect::<Answers>()
+        };
+
+        let first = answers(&[
+            ("input", ChallengePart::First, "CMZ"),
+            ("input", ChallengePart::Second, "MCD"),
+        ]);
+        let second = answers(&[
+            ("input", ChallengePart::First, "CMZ"),
+            ("input", ChallengePart::Second, "MCB"),
+            ("small", ChallengePart::First, "C"),
+        ]);
+
+        let mut out = vec![];
+        write_changes(&Answers::new(), &first, &mut out).unwrap();
+        write_changes(&first, &second, &mut out).unwrap();
+
+        let expected = concat!(
+            "  input  first   CMZ\n",
+            "  input  second  MCD\n",
+            "  input  first   CMZ  (unchanged)\n",
+            "  input  second  MCB  (was MCD)\n",
+            "  small  first   C  (new)\n",
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+        let before = Snapshot::from([(PathBuf::from("input/day05"), None)]);
+        let after = Snapshot::from([
+            (
+                PathBuf::from("input/day05"),
+                Some((SystemTime::UNIX_EPOCH, 1)),
+            ),
+            (PathBuf::from("examples/small"), None),
+        ]);
+        assert_eq!(changed_files(&before, &after), vec!["day05", "small"]);
+    }
+}